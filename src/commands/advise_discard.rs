@@ -0,0 +1,119 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use ortalab::models::advisor::{AdvisorConfig, DiscardOption, advise};
+use ortalab::models::deck::DeckKind;
+use ortalab::models::rng::SeededRng;
+use ortalib::{Card, JokerCard};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortBy {
+    /// Highest expected best score first
+    Ev,
+    /// Highest chance of reaching --target first
+    Chance,
+}
+
+#[derive(Args)]
+pub struct AdviseDiscardArgs {
+    /// YAML with `hand`, and optionally `deck` (the cards left to draw;
+    /// defaults to a standard deck minus the hand) and `jokers`
+    file: PathBuf,
+
+    /// Score the best play after redrawing has to reach
+    #[arg(long)]
+    target: Option<f64>,
+
+    #[arg(long, value_enum, default_value_t = SortBy::Ev)]
+    sort: SortBy,
+
+    /// Number of choices to list
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+
+    #[arg(long, default_value_t = AdvisorConfig::default().max_discard)]
+    max_discard: usize,
+
+    /// Enumerate redraws exactly when there are at most this many
+    #[arg(long, default_value_t = AdvisorConfig::default().exact_limit)]
+    exact_limit: usize,
+
+    /// Redraws to sample per choice otherwise
+    #[arg(long, default_value_t = AdvisorConfig::default().samples as u64,
+          value_parser = clap::value_parser!(u64).range(1..))]
+    samples: u64,
+
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+#[derive(Deserialize)]
+struct Situation {
+    hand: Vec<Card>,
+    deck: Option<Vec<Card>>,
+    #[serde(default)]
+    jokers: Vec<JokerCard>,
+}
+
+/// A standard deck without the cards already in hand.
+fn remaining_deck(hand: &[Card]) -> Vec<Card> {
+    let mut deck = DeckKind::Standard.starting_cards(&mut SeededRng::new(0));
+    for card in hand {
+        if let Some(i) = deck
+            .iter()
+            .position(|c| c.rank == card.rank && c.suit == card.suit)
+        {
+            deck.remove(i);
+        }
+    }
+    deck
+}
+
+pub fn run(args: AdviseDiscardArgs) -> Result<(), Box<dyn Error>> {
+    if args.sort == SortBy::Chance && args.target.is_none() {
+        return Err("--sort chance needs --target".into());
+    }
+    let situation: Situation = serde_yaml::from_str(&crate::read_input(&args.file)?)?;
+    let deck = situation
+        .deck
+        .unwrap_or_else(|| remaining_deck(&situation.hand));
+    let config = AdvisorConfig {
+        max_discard: args.max_discard,
+        exact_limit: args.exact_limit,
+        samples: args.samples as usize,
+        target: args.target,
+    };
+
+    let mut rng = SeededRng::new(args.seed);
+    let mut options = advise(&situation.hand, &deck, &situation.jokers, &config, &mut rng);
+    if args.sort == SortBy::Chance {
+        options.sort_by(|a, b| {
+            let chance = |o: &DiscardOption| o.hit_chance.unwrap_or(0.0);
+            chance(b)
+                .total_cmp(&chance(a))
+                .then(b.expected.total_cmp(&a.expected))
+        });
+    }
+
+    for (rank, option) in options.iter().take(args.top).enumerate() {
+        let discard = if option.discard.is_empty() {
+            "(keep hand)".to_string()
+        } else {
+            let cards: Vec<String> = option.discard.iter().map(|c| c.to_string()).collect();
+            cards.join(" ")
+        };
+        let chance = option.hit_chance.map_or(String::new(), |p| {
+            format!("  {:>5.1}% to reach target", p * 100.0)
+        });
+        let method = if option.exact { "exact" } else { "sampled" };
+        println!(
+            "{:>3}. discard {discard:<18} expected {:>10.1}{chance}  ({method}, {} draws)",
+            rank + 1,
+            option.expected,
+            option.outcomes
+        );
+    }
+    Ok(())
+}
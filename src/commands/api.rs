@@ -0,0 +1,77 @@
+//! Request handlers shared by the network front ends. They take and return
+//! JSON values so each transport only has to move bytes around.
+
+use ortalab::evaluate;
+use ortalab::models::catalog::catalog;
+use ortalab::models::round::clone_round;
+use ortalab::models::solver;
+use ortalab::models::sorce::{Op, Sorce};
+use ortalib::Round;
+use serde_json::{Value, json};
+
+/// Parses a round from YAML or JSON; JSON is valid YAML.
+pub fn parse_round(body: &str) -> Result<Round, String> {
+    serde_yaml::from_str(body).map_err(|e| format!("invalid round: {e}"))
+}
+
+fn step_json(source: &str, op: Op) -> Value {
+    let (kind, value) = match op {
+        Op::AddChips(chips) => ("add_chips", chips),
+        Op::AddMult(mult) => ("add_mult", mult),
+        Op::TimesMult(mult) => ("times_mult", mult),
+    };
+    json!({ "op": kind, "value": value, "source": source })
+}
+
+pub fn breakdown(sorce: &Sorce) -> Value {
+    let steps: Vec<Value> = sorce
+        .steps
+        .iter()
+        .map(|step| step_json(&step.source, step.op))
+        .collect();
+    let at_risk: Vec<Value> = sorce
+        .at_risk
+        .iter()
+        .map(|(card, chance)| json!({ "card": card, "chance": chance }))
+        .collect();
+    json!({
+        "hand": sorce.hand.to_string(),
+        "base_chips": sorce.card_chips,
+        "base_mult": sorce.base_mult,
+        "chips": sorce.total_chips,
+        "mult": sorce.mult,
+        "score": (sorce.total_chips * sorce.mult).floor(),
+        "steps": steps,
+        "at_risk": at_risk,
+    })
+}
+
+pub fn score(round: &Round) -> Result<Value, String> {
+    if round.cards_played.is_empty() {
+        return Err("no cards played".to_string());
+    }
+    if round.cards_played.len() > solver::MAX_PLAYED {
+        return Err(format!(
+            "{} cards played, at most {} may be",
+            round.cards_played.len(),
+            solver::MAX_PLAYED
+        ));
+    }
+    Ok(breakdown(&evaluate(clone_round(round))))
+}
+
+/// The best cards to play out of everything in the round, with the
+/// breakdown of playing them.
+pub fn solve(round: &Round) -> Result<Value, String> {
+    let solution = solver::solve(round)?;
+    Ok(json!({
+        "cards_played": solution.round.cards_played,
+        "cards_held_in_hand": solution.round.cards_held_in_hand,
+        "score": solution.score,
+        "breakdown": breakdown(&evaluate(solution.round)),
+    }))
+}
+
+pub fn catalog_json() -> Value {
+    catalog().iter().map(super::catalog::to_json).collect()
+}
@@ -0,0 +1,191 @@
+use std::error::Error;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::Args;
+use ortalab::models::consumables::{Spectral, Tarot, apply_spectral, apply_tarot};
+use ortalab::models::rng::SeededRng;
+use ortalab::models::round::clone_round;
+use ortalab::models::seed::{Seed, Stream};
+use ortalab::models::session::Zone;
+use ortalib::{Card, Round};
+
+#[derive(Args)]
+pub struct ApplyArgs {
+    /// Round to change
+    file: PathBuf,
+
+    /// Tarot to use, e.g. "The Empress" or "death"
+    #[arg(
+        long,
+        required_unless_present = "spectral",
+        conflicts_with = "spectral"
+    )]
+    tarot: Option<Tarot>,
+
+    /// Spectral to use on the played and held cards, e.g. "sigil"
+    #[arg(long)]
+    spectral: Option<Spectral>,
+
+    /// Card to use it on: `played:N` or `held:N`, from 1 (repeatable, in
+    /// selection order)
+    #[arg(long = "target")]
+    targets: Vec<CardRef>,
+
+    /// 8-character seed for the spectral's random picks; a random one is
+    /// picked and printed when omitted
+    #[arg(long)]
+    seed: Option<Seed>,
+
+    /// Write the changed round to this file
+    #[arg(long)]
+    save: Option<PathBuf>,
+}
+
+/// A card in a round, as typed on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardRef {
+    pub zone: Zone,
+    pub index: usize,
+}
+
+impl FromStr for CardRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (zone, number) = s.split_once(':').unwrap_or(("played", s));
+        let zone = match zone {
+            "played" => Zone::Played,
+            "held" => Zone::Held,
+            _ => return Err(format!("expected played:N or held:N, got `{s}`")),
+        };
+        match number.parse::<usize>() {
+            Ok(n) if n >= 1 => Ok(CardRef { zone, index: n - 1 }),
+            _ => Err(format!("`{number}` is not a card number from 1")),
+        }
+    }
+}
+
+pub fn cards_mut(round: &mut Round, zone: Zone) -> &mut Vec<Card> {
+    match zone {
+        Zone::Played => &mut round.cards_played,
+        Zone::Held => &mut round.cards_held_in_hand,
+    }
+}
+
+pub fn lookup(round: &mut Round, target: CardRef) -> Result<&mut Card, String> {
+    let zone = match target.zone {
+        Zone::Played => "played",
+        Zone::Held => "held",
+    };
+    cards_mut(round, target.zone)
+        .get_mut(target.index)
+        .ok_or_else(|| format!("there is no {zone} card #{}", target.index + 1))
+}
+
+/// The round's score, or `None` if nothing is played.
+pub fn score(round: &Round) -> Option<f64> {
+    if round.cards_played.is_empty() {
+        return None;
+    }
+    let (chips, mult) = ortalab::score(clone_round(round));
+    Some((chips * mult).floor())
+}
+
+/// Prints the score before and after a change to the round.
+pub fn print_scores(before: Option<f64>, after: Option<f64>) {
+    let show = |s: Option<f64>| s.map_or("-".to_string(), |s| s.to_string());
+    let delta = match (before, after) {
+        (Some(before), Some(after)) => format!(" ({:+})", after - before),
+        _ => String::new(),
+    };
+    println!("before: {}", show(before));
+    println!("after:  {}{delta}", show(after));
+}
+
+fn use_tarot(round: &mut Round, tarot: Tarot, refs: &[CardRef]) -> Result<(), String> {
+    let mut targets = Vec::new();
+    for target in refs {
+        targets.push(*lookup(round, *target)?);
+    }
+    let changed = apply_tarot(tarot, &targets)?;
+    for ((target, old), new) in refs.iter().zip(&targets).zip(changed) {
+        println!("{tarot}: {old} -> {new}");
+        *lookup(round, *target)? = new;
+    }
+    Ok(())
+}
+
+/// Uses a spectral on the played and held cards together, as they are all
+/// in hand before the play. Created cards are added to the held cards.
+fn use_spectral(
+    round: &mut Round,
+    spectral: Spectral,
+    refs: &[CardRef],
+    rng: &mut SeededRng,
+) -> Result<(), String> {
+    let played = round.cards_played.len();
+    let mut selected = Vec::new();
+    for target in refs {
+        lookup(round, *target)?;
+        selected.push(match target.zone {
+            Zone::Played => target.index,
+            Zone::Held => played + target.index,
+        });
+    }
+    let mut hand: Vec<Card> = round
+        .cards_played
+        .iter()
+        .chain(&round.cards_held_in_hand)
+        .copied()
+        .collect();
+    let outcome = apply_spectral(spectral, &mut hand, &selected, rng)?;
+
+    let old: Vec<Card> = round
+        .cards_played
+        .iter()
+        .chain(&round.cards_held_in_hand)
+        .copied()
+        .collect();
+    for (old, new) in old.iter().zip(&hand).filter(|(old, new)| old != new) {
+        println!("{spectral}: {old} -> {new}");
+    }
+    round.cards_held_in_hand = hand.split_off(played);
+    round.cards_played = hand;
+    for card in &outcome.destroyed {
+        println!("{spectral}: {card} destroyed");
+        for zone in [Zone::Played, Zone::Held] {
+            cards_mut(round, zone).retain(|c| c != card);
+        }
+    }
+    for card in &outcome.created {
+        println!("{spectral}: {card} created");
+    }
+    round.cards_held_in_hand.extend(&outcome.created);
+    if outcome.hand_size_change != 0 {
+        println!("{spectral}: hand size {:+}", outcome.hand_size_change);
+    }
+    Ok(())
+}
+
+pub fn run(args: ApplyArgs) -> Result<(), Box<dyn Error>> {
+    let mut round = crate::parse_round(&args.file)?;
+    let before = score(&round);
+
+    match (args.tarot, args.spectral) {
+        (Some(tarot), _) => use_tarot(&mut round, tarot, &args.targets)?,
+        (None, Some(spectral)) => {
+            let seed = args.seed.unwrap_or_else(Seed::random);
+            println!("seed: {seed}");
+            let mut rng = seed.rng(Stream::Consumables);
+            use_spectral(&mut round, spectral, &args.targets, &mut rng)?
+        }
+        (None, None) => unreachable!("clap requires --tarot or --spectral"),
+    }
+    print_scores(before, score(&round));
+
+    if let Some(path) = args.save {
+        std::fs::write(path, serde_yaml::to_string(&round)?)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use ortalab::models::attribution::{
+    self, EXACT_SHAPLEY_JOKERS, Method, card_marginals, joker_marginals, shapley,
+};
+use ortalab::models::rng::SeededRng;
+use ortalab::models::round::clone_round;
+use ortalib::Round;
+
+#[derive(Args)]
+pub struct AttributeArgs {
+    /// Round to attribute
+    file: PathBuf,
+
+    /// Orderings to sample when a lineup has more than 10 jokers
+    #[arg(long, default_value_t = 2000, value_parser = clap::value_parser!(u64).range(1..))]
+    samples: u64,
+
+    /// Seed for the sampled orderings; a plain integer rather than an
+    /// 8-character run seed, as nothing here is part of a run
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+pub fn run(args: AttributeArgs) -> Result<(), Box<dyn Error>> {
+    let round = crate::parse_round(&args.file)?;
+    if round.cards_played.is_empty() {
+        return Err("no cards played".into());
+    }
+
+    let total = attribution::total(&round);
+    let no_jokers = attribution::total(&Round {
+        jokers: Vec::new(),
+        ..clone_round(&round)
+    });
+    println!(
+        "score {} (without jokers {})",
+        total.floor(),
+        no_jokers.floor()
+    );
+
+    if !round.jokers.is_empty() {
+        let marginals = joker_marginals(&round)?;
+        let mut rng = SeededRng::new(args.seed);
+        let (values, method) = shapley(&round, args.samples as usize, &mut rng)?;
+        println!();
+        println!("{:<28} {:>12} {:>12}", "joker", "marginal", "shapley");
+        for (i, joker) in round.jokers.iter().enumerate() {
+            println!(
+                "{:<28} {:>+12.2} {:>+12.2}",
+                format!("{} {joker}", i + 1),
+                marginals[i],
+                values[i]
+            );
+        }
+        match method {
+            Method::Exact => println!("shapley values are exact"),
+            Method::Sampled(n) => println!(
+                "shapley values are estimated from {n} sampled orderings \
+                 (exact up to {EXACT_SHAPLEY_JOKERS} jokers)"
+            ),
+        }
+    }
+
+    let (played, held) = card_marginals(&round);
+    println!();
+    println!("{:<28} {:>12}", "card", "marginal");
+    for (card, value) in round.cards_played.iter().zip(played) {
+        println!("{:<28} {value:>+12.2}", format!("played {card}"));
+    }
+    for (card, value) in round.cards_held_in_hand.iter().zip(held) {
+        println!("{:<28} {value:>+12.2}", format!("held {card}"));
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::error::Error;
+
+use clap::Args;
+use ortalab::models::catalog::{JokerInfo, Rarity, Trigger, catalog};
+use serde_json::{Value, json};
+
+#[derive(Args)]
+pub struct CatalogArgs {
+    /// Only list jokers of this rarity (common, uncommon, rare)
+    #[arg(long)]
+    rarity: Option<Rarity>,
+
+    /// Only list jokers with this trigger (independent, hand-conditional,
+    /// on-scored, on-held, passive, copy)
+    #[arg(long)]
+    trigger: Option<Trigger>,
+
+    /// Only list jokers the engine implements
+    #[arg(long, conflicts_with = "unimplemented")]
+    implemented: bool,
+
+    /// Only list jokers the engine does not implement yet
+    #[arg(long)]
+    unimplemented: bool,
+
+    /// Print a JSON array instead of a table
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn to_json(info: &JokerInfo) -> Value {
+    json!({
+        "name": info.joker.to_string(),
+        "description": info.description,
+        "rarity": info.rarity.to_string(),
+        "trigger": info.trigger.to_string(),
+        "edition_timing": info.edition_timing,
+        "cost": info.cost,
+        "implemented": info.implemented,
+    })
+}
+
+pub fn run(args: CatalogArgs) -> Result<(), Box<dyn Error>> {
+    let jokers: Vec<JokerInfo> = catalog()
+        .into_iter()
+        .filter(|j| args.rarity.is_none_or(|r| j.rarity == r))
+        .filter(|j| args.trigger.is_none_or(|t| j.trigger == t))
+        .filter(|j| !args.implemented || j.implemented)
+        .filter(|j| !args.unimplemented || !j.implemented)
+        .collect();
+
+    if args.json {
+        let list: Vec<Value> = jokers.iter().map(to_json).collect();
+        println!("{}", serde_json::to_string_pretty(&list)?);
+        return Ok(());
+    }
+
+    for info in &jokers {
+        let status = if info.implemented {
+            "implemented"
+        } else {
+            "not implemented"
+        };
+        println!(
+            "{:<16} {:<8} ${:<3} {:<16} {:<15} {}",
+            info.joker.to_string(),
+            info.rarity.to_string(),
+            info.cost,
+            info.trigger.to_string(),
+            status,
+            info.description
+        );
+    }
+    Ok(())
+}
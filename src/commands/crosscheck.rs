@@ -0,0 +1,159 @@
+use std::error::Error;
+use std::panic;
+use std::path::PathBuf;
+
+use clap::Args;
+use enum_iterator::all;
+use ortalab::models::generator::{GenConfig, RoundGenerator};
+use ortalab::models::oracle;
+use ortalab::models::round::clone_round;
+use ortalab::models::shrink::shrink;
+use ortalib::{Edition, Enhancement, Round};
+
+#[derive(Args)]
+pub struct CrosscheckArgs {
+    /// Round files to check; each may contain several YAML documents
+    files: Vec<PathBuf>,
+
+    /// Also check this many generated rounds
+    #[arg(long, default_value_t = 0)]
+    generate: usize,
+
+    /// Batch number passed on to the generator, as with `gen --seed`
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// Silences the panic hook while alive, so engine panics caught by
+/// [`engine_score`] are not printed, and puts the previous hook back when
+/// dropped, including on early returns.
+pub struct QuietPanics {
+    previous: Option<PanicHook>,
+}
+
+impl QuietPanics {
+    pub fn new() -> Self {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        QuietPanics {
+            previous: Some(previous),
+        }
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            panic::set_hook(previous);
+        }
+    }
+}
+
+/// The engine's floored score, or a description of how it crashed.
+pub fn engine_score(round: &Round) -> Result<f64, String> {
+    let round = clone_round(round);
+    panic::catch_unwind(|| {
+        let (chips, mult) = ortalab::score(round);
+        (chips * mult).floor()
+    })
+    .map_err(|err| {
+        err.downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| err.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".to_string())
+    })
+}
+
+fn oracle_score(round: &Round) -> f64 {
+    let (chips, mult) = oracle::score(round);
+    (chips * mult).floor()
+}
+
+fn disagrees(round: &Round) -> bool {
+    engine_score(round) != Ok(oracle_score(round))
+}
+
+pub fn describe(score: &Result<f64, String>) -> String {
+    match score {
+        Ok(score) => score.to_string(),
+        Err(err) => format!("panic ({err})"),
+    }
+}
+
+pub fn run(args: CrosscheckArgs) -> Result<(), Box<dyn Error>> {
+    let mut rounds: Vec<(String, Round)> = Vec::new();
+    for file in &args.files {
+        for (i, round) in crate::parse_rounds(file)?.into_iter().enumerate() {
+            rounds.push((format!("{}#{}", file.display(), i + 1), round));
+        }
+    }
+
+    let config = GenConfig {
+        random_jokers: 5,
+        enhancement_chance: all::<Enhancement>().map(|e| (e, 0.05)).collect(),
+        edition_chance: all::<Edition>().map(|e| (e, 0.05)).collect(),
+        held_max: 5,
+        ..GenConfig::default()
+    };
+    let mut generator = RoundGenerator::new(config, args.seed);
+    for i in 0..args.generate {
+        rounds.push((format!("generated#{}", i + 1), generator.next_round()?));
+    }
+
+    // Engine panics are reported as mismatches, not printed by the hook.
+    let quiet = QuietPanics::new();
+
+    let mut mismatches = 0;
+    for (name, round) in &rounds {
+        if !disagrees(round) {
+            continue;
+        }
+        mismatches += 1;
+        let minimal = shrink(round, disagrees);
+        println!(
+            "MISMATCH {name}: engine {}, oracle {}",
+            describe(&engine_score(round)),
+            oracle_score(round)
+        );
+        println!(
+            "minimal reproducer (engine {}, oracle {}):",
+            describe(&engine_score(&minimal)),
+            oracle_score(&minimal)
+        );
+        print!("{}", serde_yaml::to_string(&minimal)?);
+        println!();
+    }
+
+    drop(quiet);
+    println!("checked {} rounds, {mismatches} mismatches", rounds.len());
+    if mismatches > 0 {
+        return Err(format!("{mismatches} rounds disagree with the oracle").into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[test]
+    fn quiet_panics_restores_the_previous_hook() {
+        panic::set_hook(Box::new(|_| {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+        }));
+        {
+            let _quiet = QuietPanics::new();
+            let _ = panic::catch_unwind(|| panic!("silenced"));
+        }
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+        let _ = panic::catch_unwind(|| panic!("reported"));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+        let _ = panic::take_hook();
+    }
+}
@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use clap::{Args, ValueEnum};
+use enum_iterator::all;
+use ortalab::models::generator::{GenConfig, RoundGenerator};
+use ortalib::{Edition, Enhancement, JokerCard, PokerHand};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Yaml,
+    Jsonl,
+}
+
+#[derive(Args)]
+pub struct GenArgs {
+    /// Number of rounds to generate
+    #[arg(short = 'n', long, default_value_t = 10)]
+    count: usize,
+
+    /// Numbers the batch; a plain integer rather than an 8-character run
+    /// seed, so existing batches keep their numbers
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Force the played cards to form this poker hand, e.g. "Full House"
+    #[arg(long, value_parser = parse_poker_hand)]
+    hand: Option<PokerHand>,
+
+    /// Joker included in every round, e.g. "Baron" or "Joker Foil" (repeatable)
+    #[arg(long = "joker")]
+    jokers: Vec<JokerCard>,
+
+    /// Up to this many extra random jokers per round
+    #[arg(long, default_value_t = 0)]
+    random_jokers: usize,
+
+    /// Per-card enhancement probability, e.g. Glass=0.25 (repeatable)
+    #[arg(long = "enhancement", value_parser = parse_chance::<Enhancement>)]
+    enhancements: Vec<(Enhancement, f64)>,
+
+    /// Per-card edition probability, e.g. Polychrome=0.1 (repeatable)
+    #[arg(long = "edition", value_parser = parse_chance::<Edition>)]
+    editions: Vec<(Edition, f64)>,
+
+    #[arg(long, default_value_t = 0)]
+    held_min: usize,
+
+    #[arg(long, default_value_t = 3)]
+    held_max: usize,
+
+    #[arg(long, value_enum, default_value_t = Format::Yaml)]
+    format: Format,
+}
+
+pub fn parse_poker_hand(s: &str) -> Result<PokerHand, String> {
+    all::<PokerHand>()
+        .find(|h| h.to_string().eq_ignore_ascii_case(s))
+        .ok_or_else(|| format!("Invalid PokerHand: `{s}`"))
+}
+
+fn parse_chance<T: FromStr<Err = String>>(s: &str) -> Result<(T, f64), String> {
+    let (name, chance) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=PROBABILITY, got `{s}`"))?;
+    let value = name.trim().parse()?;
+    let chance: f64 = chance
+        .trim()
+        .parse()
+        .map_err(|err| format!("invalid probability `{chance}`: {err}"))?;
+    if !(0.0..=1.0).contains(&chance) {
+        return Err(format!("probability `{chance}` is not between 0 and 1"));
+    }
+    Ok((value, chance))
+}
+
+pub fn run(args: GenArgs) -> Result<(), Box<dyn Error>> {
+    if args.held_min > args.held_max {
+        return Err("--held-min must not exceed --held-max".into());
+    }
+    if args.enhancements.iter().map(|(_, p)| p).sum::<f64>() > 1.0 {
+        return Err("enhancement probabilities add up to more than 1".into());
+    }
+    if args.editions.iter().map(|(_, p)| p).sum::<f64>() > 1.0 {
+        return Err("edition probabilities add up to more than 1".into());
+    }
+
+    let config = GenConfig {
+        hand: args.hand,
+        jokers: args.jokers,
+        random_jokers: args.random_jokers,
+        enhancement_chance: args.enhancements,
+        edition_chance: args.editions,
+        held_min: args.held_min,
+        held_max: args.held_max,
+    };
+    let mut generator = RoundGenerator::new(config, args.seed);
+
+    for _ in 0..args.count {
+        let round = generator.next_round()?;
+        match args.format {
+            Format::Yaml => print!("---\n{}", serde_yaml::to_string(&round)?),
+            Format::Jsonl => println!("{}", serde_json::to_string(&round)?),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::error::Error;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use clap::Args;
+use ortalab::models::oracle;
+use ortalab::models::shrink::shrink;
+use ortalib::Round;
+
+use super::crosscheck::{QuietPanics, describe, engine_score};
+
+#[derive(Args)]
+pub struct MinimizeArgs {
+    file: PathBuf,
+
+    /// Score the round should have (e.g. read off a game screenshot). It is
+    /// checked against the reference oracle, which then judges smaller rounds.
+    #[arg(long, conflicts_with = "predicate")]
+    expected: Option<f64>,
+
+    /// Shell command that exits 0 while a candidate round, given as YAML on
+    /// stdin, still shows the problem
+    #[arg(long)]
+    predicate: Option<String>,
+}
+
+/// An engine panic counts as disagreeing, so the round is shrunk to the
+/// smallest one that still crashes.
+fn disagrees_with_oracle(round: &Round) -> bool {
+    let (chips, mult) = oracle::score(round);
+    engine_score(round) != Ok((chips * mult).floor())
+}
+
+fn run_predicate(command: &str, round: &Round) -> bool {
+    let Ok(yaml) = serde_yaml::to_string(round) else {
+        return false;
+    };
+    let Ok(mut child) = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(yaml.as_bytes());
+    }
+    child.wait().is_ok_and(|status| status.success())
+}
+
+pub fn run(args: MinimizeArgs) -> Result<(), Box<dyn Error>> {
+    let round = crate::parse_round(&args.file)?;
+    // Engine panics are judged by the predicate or the oracle, not printed
+    // by the hook.
+    let quiet = QuietPanics::new();
+    let before = engine_score(&round);
+
+    let minimal = if let Some(command) = &args.predicate {
+        if !run_predicate(command, &round) {
+            return Err("the predicate does not hold for the original round".into());
+        }
+        shrink(&round, |r| run_predicate(command, r))
+    } else {
+        if let Some(expected) = args.expected {
+            if before == Ok(expected) {
+                return Err(format!("the round already scores {expected}").into());
+            }
+            let (chips, mult) = oracle::score(&round);
+            let reference = (chips * mult).floor();
+            if reference != expected {
+                return Err(format!(
+                    "the oracle scores this round {reference}, not {expected}, \
+                     so it cannot judge smaller rounds; use --predicate instead"
+                )
+                .into());
+            }
+        } else if !disagrees_with_oracle(&round) {
+            return Err(format!(
+                "engine and oracle agree on this round ({})",
+                describe(&before)
+            )
+            .into());
+        }
+        shrink(&round, disagrees_with_oracle)
+    };
+
+    let (chips, mult) = oracle::score(&minimal);
+    eprintln!(
+        "minimized from {} to {} cards and {} to {} jokers (engine {}, oracle {})",
+        round.cards_played.len() + round.cards_held_in_hand.len(),
+        minimal.cards_played.len() + minimal.cards_held_in_hand.len(),
+        round.jokers.len(),
+        minimal.jokers.len(),
+        describe(&engine_score(&minimal)),
+        (chips * mult).floor()
+    );
+    drop(quiet);
+    print!("{}", serde_yaml::to_string(&minimal)?);
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use std::error::Error;
+
+use clap::Subcommand;
+
+pub mod advise_discard;
+pub mod api;
+pub mod apply;
+pub mod attribute;
+pub mod catalog;
+pub mod crosscheck;
+pub mod generate;
+pub mod minimize;
+pub mod play_blind;
+pub mod repl;
+pub mod rpc;
+pub mod serve;
+pub mod shop;
+pub mod simulate;
+pub mod tui;
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print N seeded random rounds
+    Gen(generate::GenArgs),
+    /// Score rounds with both the engine and the reference oracle and report disagreements
+    Crosscheck(crosscheck::CrosscheckArgs),
+    /// Shrink a round to the smallest one that still shows a scoring discrepancy
+    Minimize(minimize::MinimizeArgs),
+    /// List every joker with its description, rarity, cost, trigger and implementation status
+    Catalog(catalog::CatalogArgs),
+    /// Edit a round interactively and watch the score change
+    Repl(repl::ReplArgs),
+    /// Build a round in a full-screen terminal UI with a live score breakdown
+    Tui(tui::TuiArgs),
+    /// Serve scoring, solving and the joker catalog as JSON over local HTTP
+    Serve(serve::ServeArgs),
+    /// Answer line-delimited JSON-RPC 2.0 requests on stdin
+    Rpc(rpc::RpcArgs),
+    /// Report how much each joker and card contributes to a round's score
+    Attribute(attribute::AttributeArgs),
+    /// Play a blind from a deck, following plays and discards from a script
+    PlayBlind(play_blind::PlayBlindArgs),
+    /// Rank discard choices by the expected best score after redrawing
+    AdviseDiscard(advise_discard::AdviseDiscardArgs),
+    /// Use a consumable on cards in a round and compare the scores
+    Apply(apply::ApplyArgs),
+    /// Browse a seeded joker shop, buying, selling and rerolling from a script
+    Shop(shop::ShopArgs),
+    /// Simulate whole runs with a strategy and report how far they get
+    Simulate(simulate::SimulateArgs),
+}
+
+pub fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Gen(args) => generate::run(args),
+        Command::Crosscheck(args) => crosscheck::run(args),
+        Command::Minimize(args) => minimize::run(args),
+        Command::Catalog(args) => catalog::run(args),
+        Command::Repl(args) => repl::run(args),
+        Command::Tui(args) => tui::run(args),
+        Command::Serve(args) => serve::run(args),
+        Command::Rpc(args) => rpc::run(args),
+        Command::Attribute(args) => attribute::run(args),
+        Command::PlayBlind(args) => play_blind::run(args),
+        Command::AdviseDiscard(args) => advise_discard::run(args),
+        Command::Apply(args) => apply::run(args),
+        Command::Shop(args) => shop::run(args),
+        Command::Simulate(args) => simulate::run(args),
+    }
+}
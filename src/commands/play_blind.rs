@@ -0,0 +1,242 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Args;
+use ortalab::models::ante::{BlindKind, Stake, required_score};
+use ortalab::models::blind::{Blind, BlindConfig, BlindStatus};
+use ortalab::models::consumables::Spectral;
+use ortalab::models::deck::{Deck, DeckKind};
+use ortalab::models::economy::{MoneyJoker, RoundEnd, STARTING_MONEY, payout, total};
+use ortalab::models::seed::{Seed, Stream};
+use ortalib::{Card, JokerCard};
+use serde_json::{Value, json};
+
+#[derive(Args)]
+pub struct PlayBlindArgs {
+    /// Script with one action per line: `play 1 3 5`, `discard 2 4`,
+    /// `spectral aura 2`; card numbers refer to the current hand, from 1.
+    /// `#` starts a comment.
+    script: PathBuf,
+
+    /// Starting deck (standard, abandoned, checkered, erratic, painted)
+    #[arg(long, default_value = "standard")]
+    deck: DeckKind,
+
+    /// 8-character seed, e.g. 7LB2WVPK; a random one is picked and
+    /// printed when omitted
+    #[arg(long)]
+    seed: Option<Seed>,
+
+    /// Score needed to win the blind; defaults to the requirement for
+    /// `--blind` of `--ante` at `--stake`
+    #[arg(long)]
+    target: Option<f64>,
+
+    #[arg(long, default_value_t = 1)]
+    ante: u32,
+
+    /// Which blind of the ante (small, big, boss)
+    #[arg(long, default_value = "small")]
+    blind: BlindKind,
+
+    /// Stake (white, red, green, black, blue, purple, orange, gold)
+    #[arg(long, default_value = "white")]
+    stake: Stake,
+
+    #[arg(long, default_value_t = BlindConfig::default().hands)]
+    hands: usize,
+
+    /// Discards before the stake's modifier
+    #[arg(long, default_value_t = BlindConfig::default().discards)]
+    discards: usize,
+
+    /// Hand size before the deck's modifier
+    #[arg(long, default_value_t = BlindConfig::default().hand_size)]
+    hand_size: usize,
+
+    /// Joker in play, e.g. "Baron" or "Joker Foil" (repeatable)
+    #[arg(long = "joker")]
+    jokers: Vec<JokerCard>,
+
+    /// Money held before the blind, for interest
+    #[arg(long, default_value_t = STARTING_MONEY)]
+    money: i64,
+
+    /// Money joker owned (golden-joker, cloud9, delayed-gratification,
+    /// to-the-moon; repeatable)
+    #[arg(long = "money-joker")]
+    money_jokers: Vec<MoneyJoker>,
+
+    /// Print the outcome and payout as JSON instead of a play-by-play
+    #[arg(long)]
+    json: bool,
+}
+
+fn show_hand(hand: &[Card]) -> String {
+    hand.iter()
+        .enumerate()
+        .map(|(i, c)| format!("{}:{c}", i + 1))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_indices(args: &[&str]) -> Result<Vec<usize>, String> {
+    args.iter()
+        .map(|a| match a.parse::<usize>() {
+            Ok(n) if n >= 1 => Ok(n - 1),
+            _ => Err(format!("`{a}` is not a card number")),
+        })
+        .collect()
+}
+
+pub fn run(args: PlayBlindArgs) -> Result<(), Box<dyn Error>> {
+    let script = fs::read_to_string(&args.script)?;
+    let hand_size = args.hand_size as i32 + args.deck.hand_size_modifier();
+    let discards = args.discards as i32 + args.stake.discard_modifier();
+    let config = BlindConfig {
+        target: args
+            .target
+            .unwrap_or_else(|| required_score(args.ante, args.blind, args.stake)),
+        hands: args.hands,
+        discards: discards.max(0) as usize,
+        hand_size: hand_size.max(1) as usize,
+    };
+    let say = |line: String| {
+        if !args.json {
+            println!("{line}");
+        }
+    };
+    let seed = args.seed.unwrap_or_else(Seed::random);
+    say(format!(
+        "{} of Ante {} at {}: {} to win (seed {seed})",
+        args.blind, args.ante, args.stake, config.target
+    ));
+    let deck = Deck::new(args.deck, seed.stream_seed(Stream::Deck));
+    let mut blind = Blind::new(config, deck, args.jokers.clone(), seed.rng(Stream::Glass));
+    let mut consumables = seed.rng(Stream::Consumables);
+
+    for (n, line) in script.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&action, rest)) = words.split_first() else {
+            continue;
+        };
+        if blind.status() != BlindStatus::InProgress {
+            return Err(format!("line {}: the blind is already over", n + 1).into());
+        }
+
+        say(format!("hand: {}", show_hand(blind.hand())));
+        if action == "spectral" {
+            let (name, rest) = rest
+                .split_first()
+                .ok_or_else(|| format!("line {}: spectral needs a name", n + 1))?;
+            let spectral: Spectral = name.parse().map_err(|e| format!("line {}: {e}", n + 1))?;
+            let indices = parse_indices(rest).map_err(|e| format!("line {}: {e}", n + 1))?;
+            let outcome = blind
+                .use_spectral(spectral, &indices, &mut consumables)
+                .map_err(|e| format!("line {}: {e}", n + 1))?;
+            say(format!("{spectral}: {}", show_hand(blind.hand())));
+            for card in &outcome.destroyed {
+                say(format!("  {card} destroyed"));
+            }
+            for card in &outcome.created {
+                say(format!("  {card} created"));
+            }
+            if outcome.hand_size_change != 0 {
+                say(format!("  hand size {:+}", outcome.hand_size_change));
+            }
+            continue;
+        }
+        let indices = parse_indices(rest).map_err(|e| format!("line {}: {e}", n + 1))?;
+        let selected: Vec<String> = indices
+            .iter()
+            .filter_map(|&i| blind.hand().get(i))
+            .map(|c| c.to_string())
+            .collect();
+        let selected = selected.join(" ");
+        match action {
+            "play" => {
+                let sorce = blind
+                    .play(&indices)
+                    .map_err(|e| format!("line {}: {e}", n + 1))?;
+                say(format!(
+                    "play {selected}: {} for {} (total {}/{})",
+                    sorce.hand,
+                    (sorce.total_chips * sorce.mult).floor(),
+                    blind.score(),
+                    blind.config.target
+                ));
+                for card in &sorce.destroyed {
+                    say(format!("  {card} shattered"));
+                }
+            }
+            "discard" => {
+                blind
+                    .discard(&indices)
+                    .map_err(|e| format!("line {}: {e}", n + 1))?;
+                say(format!("discard {selected}"));
+            }
+            _ => return Err(format!("line {}: unknown action `{action}`", n + 1).into()),
+        }
+    }
+
+    let status = match blind.status() {
+        BlindStatus::Won => "won",
+        BlindStatus::Lost => "lost",
+        BlindStatus::InProgress => "in progress",
+    };
+    let full_deck = blind.full_deck();
+    let earnings = if blind.status() == BlindStatus::Won {
+        let end = RoundEnd {
+            blind: args.blind,
+            stake: args.stake,
+            money: args.money,
+            hands_left: blind.hands_left(),
+            discards_left: blind.discards_left(),
+            discards_used: blind.discards_used(),
+            full_deck: &full_deck,
+        };
+        payout(&end, &args.money_jokers)
+    } else {
+        Vec::new()
+    };
+    let earned = total(&earnings);
+
+    if args.json {
+        let sources: Vec<Value> = earnings
+            .iter()
+            .map(|e| json!({ "source": e.source, "amount": e.amount }))
+            .collect();
+        let summary = json!({
+            "seed": seed.to_string(),
+            "status": status,
+            "score": blind.score(),
+            "target": blind.config.target,
+            "hands_left": blind.hands_left(),
+            "discards_left": blind.discards_left(),
+            "payout": { "total": earned, "sources": sources },
+            "money": args.money + earned,
+        });
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+    println!(
+        "{status}: {}/{} with {} hands and {} discards left",
+        blind.score(),
+        blind.config.target,
+        blind.hands_left(),
+        blind.discards_left()
+    );
+    if blind.status() == BlindStatus::Won {
+        for earning in &earnings {
+            println!("  ${:<3} {}", earning.amount, earning.source);
+        }
+        println!(
+            "cash out ${earned}: ${} -> ${}",
+            args.money,
+            args.money + earned
+        );
+    }
+    Ok(())
+}
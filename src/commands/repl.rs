@@ -0,0 +1,361 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use clap::Args;
+use enum_iterator::{Sequence, all};
+use ortalab::models::session::{Session, Zone};
+use ortalib::{Card, Edition, Enhancement, Joker, JokerCard, Rank, Round, Suit};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use super::crosscheck::QuietPanics;
+
+#[derive(Args)]
+pub struct ReplArgs {
+    /// Round to start from; starts empty if omitted
+    file: Option<PathBuf>,
+}
+
+const HELP: &str = "\
+play <card>...                      add cards to the played hand (A♥ or AH)
+hold <card>...                      add cards to the held hand
+remove [played|held] <n>            remove card n
+move [played|held] <n>              move card n to the other hand
+enhance [played|held] <n> <name>    set an enhancement, or `none`
+edition [played|held] <n> <name>    set an edition, or `none`
+joker add <name> [edition]          append a joker
+joker remove <n>                    remove joker n
+joker move <from> <to>              move a joker to another slot
+joker edition <n> <name>            set a joker's edition, or `none`
+undo                                undo the last change
+score | explain | show              print the score, breakdown or round
+save <file>                         write the round as YAML
+quit";
+
+const COMMANDS: [&str; 13] = [
+    "play", "hold", "remove", "move", "enhance", "edition", "joker", "undo", "score", "explain",
+    "show", "save", "quit",
+];
+
+/// Matches a name case-insensitively against every variant's display name.
+fn parse_variant<T: Sequence + Display>(name: &str, what: &str) -> Result<T, String> {
+    all::<T>()
+        .find(|v| v.to_string().eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("unknown {what} `{name}`"))
+}
+
+fn parse_optional<T: Sequence + Display>(name: &str, what: &str) -> Result<Option<T>, String> {
+    if name.eq_ignore_ascii_case("none") {
+        Ok(None)
+    } else {
+        parse_variant(name, what).map(Some)
+    }
+}
+
+/// Accepts `A♥` as well as the easier to type `AH` / `10s`.
+fn parse_card(token: &str) -> Result<Card, String> {
+    let symbol = match token.chars().last().map(|c| c.to_ascii_uppercase()) {
+        Some('S') => Some('♠'),
+        Some('H') => Some('♥'),
+        Some('C') => Some('♣'),
+        Some('D') => Some('♦'),
+        _ => None,
+    };
+    let token = match symbol {
+        Some(symbol) => format!("{}{symbol}", &token[..token.len() - 1]),
+        None => token.to_string(),
+    };
+    token.to_uppercase().parse()
+}
+
+fn parse_index(token: Option<&&str>) -> Result<usize, String> {
+    let token = token.ok_or("missing card or joker number")?;
+    match token.parse::<usize>() {
+        Ok(n) if n >= 1 => Ok(n - 1),
+        _ => Err(format!("`{token}` is not a number from 1")),
+    }
+}
+
+/// Splits an optional leading `played` / `held` off the arguments.
+fn parse_zone<'a>(args: &'a [&'a str]) -> (Zone, &'a [&'a str]) {
+    match args.first() {
+        Some(&"held") => (Zone::Held, &args[1..]),
+        Some(&"played") => (Zone::Played, &args[1..]),
+        _ => (Zone::Played, args),
+    }
+}
+
+fn parse_joker(words: &[&str]) -> Result<JokerCard, String> {
+    if let Some((last, name)) = words.split_last()
+        && let Ok(edition) = parse_variant::<Edition>(last, "edition")
+        && !name.is_empty()
+    {
+        let joker = parse_variant::<Joker>(&name.join(" "), "joker")?;
+        return Ok(JokerCard::new(joker, Some(edition)));
+    }
+    let joker = parse_variant::<Joker>(&words.join(" "), "joker")?;
+    Ok(JokerCard::new(joker, None))
+}
+
+enum Outcome {
+    Changed,
+    Unchanged,
+    Quit,
+}
+
+fn print_round(round: &Round) {
+    let list = |cards: &[Card]| {
+        cards
+            .iter()
+            .enumerate()
+            .map(|(i, c)| format!("{}:{c}", i + 1))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+    println!("played: {}", list(&round.cards_played));
+    println!("held:   {}", list(&round.cards_held_in_hand));
+    let jokers: Vec<String> = round
+        .jokers
+        .iter()
+        .enumerate()
+        .map(|(i, j)| format!("{}:{j}", i + 1))
+        .collect();
+    println!("jokers: {}", jokers.join("  "));
+}
+
+fn execute(session: &mut Session, line: &str) -> Result<Outcome, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((&command, args)) = words.split_first() else {
+        return Ok(Outcome::Unchanged);
+    };
+
+    match command {
+        "play" | "hold" => {
+            let cards = args
+                .iter()
+                .map(|t| parse_card(t))
+                .collect::<Result<Vec<_>, _>>()?;
+            let zone = if command == "play" {
+                Zone::Played
+            } else {
+                Zone::Held
+            };
+            session.add_cards(zone, &cards)?;
+        }
+        "remove" => {
+            let (zone, rest) = parse_zone(args);
+            session.remove_card(zone, parse_index(rest.first())?)?;
+        }
+        "move" => {
+            let (zone, rest) = parse_zone(args);
+            session.move_card(zone, parse_index(rest.first())?)?;
+        }
+        "enhance" => {
+            let (zone, rest) = parse_zone(args);
+            let name = rest.get(1).ok_or("missing enhancement")?;
+            let enhancement = parse_optional::<Enhancement>(name, "enhancement")?;
+            session.set_enhancement(zone, parse_index(rest.first())?, enhancement)?;
+        }
+        "edition" => {
+            let (zone, rest) = parse_zone(args);
+            let name = rest.get(1).ok_or("missing edition")?;
+            let edition = parse_optional::<Edition>(name, "edition")?;
+            session.set_edition(zone, parse_index(rest.first())?, edition)?;
+        }
+        "joker" => match args.first() {
+            Some(&"add") => session.add_joker(parse_joker(&args[1..])?),
+            Some(&"remove") => {
+                session.remove_joker(parse_index(args.get(1))?)?;
+            }
+            Some(&"move") => {
+                session.move_joker(parse_index(args.get(1))?, parse_index(args.get(2))?)?
+            }
+            Some(&"edition") => {
+                let name = args.get(2).ok_or("missing edition")?;
+                let edition = parse_optional::<Edition>(name, "edition")?;
+                session.set_joker_edition(parse_index(args.get(1))?, edition)?;
+            }
+            _ => return Err("usage: joker add|remove|move|edition ...".to_string()),
+        },
+        "undo" => {
+            if !session.undo() {
+                return Err("nothing to undo".to_string());
+            }
+        }
+        "score" => {
+            match session.score()? {
+                Some(score) => println!("{score}"),
+                None => println!("no cards played"),
+            }
+            return Ok(Outcome::Unchanged);
+        }
+        "explain" => {
+            match session.evaluate()? {
+                Some(sorce) => crate::print_explain(&sorce),
+                None => println!("no cards played"),
+            }
+            return Ok(Outcome::Unchanged);
+        }
+        "show" => {
+            print_round(&session.round);
+            return Ok(Outcome::Unchanged);
+        }
+        "save" => {
+            let path = args.first().ok_or("missing file name")?;
+            let yaml = serde_yaml::to_string(&session.round).map_err(|e| e.to_string())?;
+            std::fs::write(path, yaml).map_err(|e| e.to_string())?;
+            return Ok(Outcome::Unchanged);
+        }
+        "help" => {
+            println!("{HELP}");
+            return Ok(Outcome::Unchanged);
+        }
+        "quit" | "exit" => return Ok(Outcome::Quit),
+        _ => return Err(format!("unknown command `{command}`, try `help`")),
+    }
+    Ok(Outcome::Changed)
+}
+
+/// Tab completion for commands, card names, joker names and modifiers.
+struct ReplHelper {
+    cards: Vec<String>,
+    jokers: Vec<String>,
+}
+
+impl ReplHelper {
+    fn new() -> Self {
+        let cards = all::<Rank>()
+            .flat_map(|rank| all::<Suit>().map(move |suit| format!("{rank}{suit}")))
+            .collect();
+        let jokers = all::<Joker>().map(|j| j.to_string()).collect();
+        Self { cards, jokers }
+    }
+
+    fn candidates(&self, before: &[&str]) -> Vec<String> {
+        match before {
+            [] => COMMANDS.iter().map(|c| c.to_string()).collect(),
+            ["play" | "hold", ..] => self.cards.clone(),
+            ["joker"] => ["add", "remove", "move", "edition"]
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            ["joker", "edition", _] | ["edition", .., _] => names::<Edition>()
+                .into_iter()
+                .chain(["none".into()])
+                .collect(),
+            ["enhance", .., _] => names::<Enhancement>()
+                .into_iter()
+                .chain(["none".into()])
+                .collect(),
+            ["remove" | "move" | "enhance" | "edition"] => vec!["played".into(), "held".into()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn names<T: Sequence + Display>() -> Vec<String> {
+    all::<T>().map(|v| v.to_string()).collect()
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        // Joker names contain spaces, so everything after `joker add` is one word.
+        if let Some(partial) = line.strip_prefix("joker add ") {
+            let start = pos - partial.len();
+            let matches = self
+                .jokers
+                .iter()
+                .filter(|j| j.to_lowercase().starts_with(&partial.to_lowercase()))
+                .cloned()
+                .collect();
+            return Ok((start, matches));
+        }
+
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let partial = &line[start..];
+        let before: Vec<&str> = line[..start].split_whitespace().collect();
+        let matches = self
+            .candidates(&before)
+            .into_iter()
+            .filter(|c| c.to_lowercase().starts_with(&partial.to_lowercase()))
+            .collect();
+        Ok((start, matches))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+fn describe_score(score: Option<f64>) -> String {
+    score.map_or("-".to_string(), |s| s.to_string())
+}
+
+pub fn run(args: ReplArgs) -> Result<(), Box<dyn Error>> {
+    let round = match &args.file {
+        Some(file) => crate::parse_round(file)?,
+        None => Round {
+            cards_played: Vec::new(),
+            cards_held_in_hand: Vec::new(),
+            jokers: Vec::new(),
+        },
+    };
+    let mut session = Session::new(round);
+    let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ReplHelper::new()));
+
+    // Engine panics are reported as errors, not printed by the hook.
+    let _quiet = QuietPanics::new();
+
+    println!("type `help` for commands");
+    let mut last_score = session.score().unwrap_or(None);
+    loop {
+        let line = match editor.readline("ortalab> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        };
+        editor.add_history_entry(line.as_str())?;
+
+        match execute(&mut session, &line) {
+            Ok(Outcome::Changed) => match session.score() {
+                Ok(score) => {
+                    let delta = match (score, last_score) {
+                        (Some(now), Some(before)) => format!(" ({:+})", now - before),
+                        _ => String::new(),
+                    };
+                    println!("score: {}{delta}", describe_score(score));
+                    last_score = score;
+                }
+                Err(err) => {
+                    println!("error: {err}");
+                    last_score = None;
+                }
+            },
+            Ok(Outcome::Unchanged) => {}
+            Ok(Outcome::Quit) => break,
+            Err(err) => println!("error: {err}"),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,229 @@
+use std::error::Error;
+use std::io::{BufRead, Write, stdin, stdout};
+use std::panic::{self, AssertUnwindSafe};
+
+use clap::Args;
+use ortalab::models::catalog;
+use ortalab::models::solver::MAX_PLAYED;
+use ortalib::Round;
+use serde_json::{Value, json};
+
+use super::api;
+
+#[derive(Args)]
+pub struct RpcArgs {}
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// State built once and shared by every call.
+struct Context {
+    catalog: Value,
+}
+
+/// `params.round` may be a round object or a YAML string.
+fn round_param(params: &Value) -> Result<Round, RpcError> {
+    let round = params
+        .get("round")
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "missing params.round"))?;
+    let parsed = match round {
+        Value::String(yaml) => api::parse_round(yaml),
+        other => serde_json::from_value(other.clone()).map_err(|e| format!("invalid round: {e}")),
+    };
+    parsed.map_err(|e| RpcError::new(INVALID_PARAMS, e))
+}
+
+fn validate(round: &Round) -> Value {
+    let mut errors = Vec::new();
+    if round.cards_played.is_empty() {
+        errors.push("no cards played".to_string());
+    }
+    if round.cards_played.len() > MAX_PLAYED {
+        errors.push(format!(
+            "{} cards played, at most {MAX_PLAYED} may be",
+            round.cards_played.len()
+        ));
+    }
+    let warnings: Vec<String> = catalog::unimplemented(&round.jokers)
+        .iter()
+        .map(|joker| format!("{joker} is not implemented and will be ignored"))
+        .collect();
+    json!({ "valid": errors.is_empty(), "errors": errors, "warnings": warnings })
+}
+
+fn call(context: &Context, method: &str, params: &Value) -> Result<Value, RpcError> {
+    let invalid = |e: String| RpcError::new(INVALID_PARAMS, e);
+    match method {
+        "score" => {
+            let breakdown = api::score(&round_param(params)?).map_err(invalid)?;
+            Ok(json!({ "score": breakdown["score"] }))
+        }
+        "explain" => api::score(&round_param(params)?).map_err(invalid),
+        "solve" => api::solve(&round_param(params)?).map_err(invalid),
+        "validate" => Ok(validate(&round_param(params)?)),
+        "catalog" => Ok(context.catalog.clone()),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method `{method}`"),
+        )),
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// Answers one request object; `None` for notifications, which get no reply.
+fn handle(context: &Context, request: &Value) -> Option<Value> {
+    let id = request.get("id").cloned();
+    let method = request.get("method").and_then(Value::as_str);
+    let (Some(method), Some("2.0")) = (method, request.get("jsonrpc").and_then(Value::as_str))
+    else {
+        let error = RpcError::new(INVALID_REQUEST, "expected jsonrpc 2.0 and a method");
+        return Some(error_response(id.unwrap_or(Value::Null), error));
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| call(context, method, &params)))
+        .unwrap_or_else(|_| Err(RpcError::new(INTERNAL_ERROR, "the engine panicked")));
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => error_response(id, error),
+    })
+}
+
+fn handle_line(context: &Context, line: &str) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = RpcError::new(PARSE_ERROR, format!("parse error: {e}"));
+            return Some(error_response(Value::Null, error));
+        }
+    };
+    match request {
+        Value::Array(batch) if batch.is_empty() => Some(error_response(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "empty batch"),
+        )),
+        Value::Array(batch) => {
+            let replies: Vec<Value> = batch.iter().filter_map(|r| handle(context, r)).collect();
+            (!replies.is_empty()).then_some(Value::Array(replies))
+        }
+        request => handle(context, &request),
+    }
+}
+
+/// Lines arrive as raw bytes so one that is not UTF-8 gets a parse error
+/// like any other malformed request instead of ending the loop.
+fn handle_bytes(context: &Context, line: &[u8]) -> Option<Value> {
+    match std::str::from_utf8(line) {
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(line) => handle_line(context, line),
+        Err(e) => {
+            let error = RpcError::new(PARSE_ERROR, format!("parse error: {e}"));
+            Some(error_response(Value::Null, error))
+        }
+    }
+}
+
+pub fn run(_args: RpcArgs) -> Result<(), Box<dyn Error>> {
+    let context = Context {
+        catalog: api::catalog_json(),
+    };
+    let mut out = stdout().lock();
+    for line in stdin().lock().split(b'\n') {
+        if let Some(reply) = handle_bytes(&context, &line?) {
+            writeln!(out, "{reply}")?;
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> Context {
+        Context {
+            catalog: Value::Null,
+        }
+    }
+
+    fn error_code(reply: &Value) -> Option<i64> {
+        reply["error"]["code"].as_i64()
+    }
+
+    #[test]
+    fn invalid_utf8_is_a_parse_error() {
+        let reply = handle_bytes(&context(), b"{\"jsonrpc\": \"2.0\", \xff}").unwrap();
+        assert_eq!(error_code(&reply), Some(PARSE_ERROR));
+        assert_eq!(reply["id"], Value::Null);
+    }
+
+    #[test]
+    fn blank_lines_get_no_reply() {
+        assert!(handle_bytes(&context(), b"  \r").is_none());
+    }
+
+    #[test]
+    fn score_rejects_too_many_played_cards() {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "score",
+            "params": { "round": "cards_played: [A♠, A♠, A♠, A♠, A♠, A♠]\ncards_held_in_hand: []\njokers: []" },
+        });
+        let reply = handle_bytes(&context(), request.to_string().as_bytes()).unwrap();
+        assert_eq!(error_code(&reply), Some(INVALID_PARAMS));
+    }
+
+    #[test]
+    fn score_and_explain_reject_an_empty_hand() {
+        for method in ["score", "explain"] {
+            let request = json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": method,
+                "params": { "round": "cards_played: []\ncards_held_in_hand: [K♠]\njokers: []" },
+            });
+            let reply = handle_bytes(&context(), request.to_string().as_bytes()).unwrap();
+            assert_eq!(error_code(&reply), Some(INVALID_PARAMS));
+            assert_eq!(reply["error"]["message"], "no cards played");
+        }
+    }
+
+    #[test]
+    fn score_answers_a_valid_round() {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "score",
+            "params": { "round": "cards_played: [A♠, A♥]\ncards_held_in_hand: []\njokers: []" },
+        });
+        let reply = handle_bytes(&context(), request.to_string().as_bytes()).unwrap();
+        assert_eq!(reply["result"]["score"], json!(64.0));
+    }
+}
@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::net::IpAddr;
+use std::panic::{self, AssertUnwindSafe};
+
+use clap::Args;
+use serde_json::{Value, json};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::api;
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Port to listen on
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+
+    /// Address to bind; the default only accepts connections from this machine
+    #[arg(long, default_value = "127.0.0.1")]
+    host: IpAddr,
+}
+
+fn route(method: &Method, path: &str, body: &str) -> Result<Value, (u16, String)> {
+    let bad_request = |err: String| (400, err);
+    match (method, path) {
+        (Method::Get, "/health") => Ok(json!({ "status": "ok" })),
+        (Method::Get, "/catalog") => Ok(api::catalog_json()),
+        (Method::Post, "/score") => {
+            let round = api::parse_round(body).map_err(bad_request)?;
+            api::score(&round).map_err(bad_request)
+        }
+        (Method::Post, "/solve") => {
+            let round = api::parse_round(body).map_err(bad_request)?;
+            api::solve(&round).map_err(bad_request)
+        }
+        (_, "/health" | "/catalog" | "/score" | "/solve") => {
+            Err((405, format!("{method} is not allowed on {path}")))
+        }
+        _ => Err((404, format!("no such endpoint: {path}"))),
+    }
+}
+
+fn handle(mut request: Request) -> Result<(), Box<dyn Error>> {
+    let mut body = String::new();
+    request.as_reader().read_to_string(&mut body)?;
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    let method = request.method().clone();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| route(&method, &path, &body)))
+        .unwrap_or_else(|_| Err((500, "the engine panicked on this round".to_string())));
+    let (status, value) = match result {
+        Ok(value) => (200, value),
+        Err((status, error)) => (status, json!({ "error": error })),
+    };
+    eprintln!("{method} {path} {status}");
+
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+    let response = Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+    request.respond(response)?;
+    Ok(())
+}
+
+pub fn run(args: ServeArgs) -> Result<(), Box<dyn Error>> {
+    let server = Server::http((args.host, args.port)).map_err(|e| e.to_string())?;
+    eprintln!("listening on http://{}", server.server_addr());
+    for request in server.incoming_requests() {
+        if let Err(err) = handle(request) {
+            eprintln!("error: {err}");
+        }
+    }
+    Ok(())
+}
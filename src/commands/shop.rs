@@ -0,0 +1,131 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Args;
+use ortalab::models::economy::STARTING_MONEY;
+use ortalab::models::round::clone_round;
+use ortalab::models::seed::{Seed, Stream};
+use ortalab::models::shop::{JOKER_SLOTS, Shop, ShopConfig, cost, sell, sell_value};
+use ortalib::{JokerCard, Round};
+
+use super::apply::score;
+
+#[derive(Args)]
+pub struct ShopArgs {
+    /// Script with one action per line: `buy 1`, `sell 2`, `reroll`; offers
+    /// and jokers are numbered from 1. `#` starts a comment. Without a
+    /// script the opening offers are listed.
+    script: Option<PathBuf>,
+
+    #[arg(long, default_value_t = STARTING_MONEY)]
+    money: i64,
+
+    /// Round whose jokers are owned; each offer is scored as if added to it
+    #[arg(long)]
+    round: Option<PathBuf>,
+
+    /// Owned joker, e.g. "Baron" or "Joker Foil" (repeatable)
+    #[arg(long = "joker")]
+    jokers: Vec<JokerCard>,
+
+    #[arg(long, default_value_t = JOKER_SLOTS)]
+    joker_slots: usize,
+
+    /// Jokers on offer at a time
+    #[arg(long, default_value_t = ShopConfig::default().slots)]
+    slots: usize,
+
+    /// 8-character seed, e.g. 7LB2WVPK; a random one is picked and
+    /// printed when omitted
+    #[arg(long)]
+    seed: Option<Seed>,
+}
+
+/// The round scored with `jokers` instead of its own.
+fn score_with(round: &Round, jokers: &[JokerCard]) -> Option<f64> {
+    let mut round = clone_round(round);
+    round.jokers = jokers.to_vec();
+    score(&round)
+}
+
+fn show(shop: &Shop, jokers: &[JokerCard], money: i64, round: Option<&Round>) {
+    println!("money: ${money}, reroll: ${}", shop.reroll_cost());
+    let current = round.and_then(|r| score_with(r, jokers));
+    for (i, offer) in shop.offers().iter().enumerate() {
+        let with = round.and_then(|r| {
+            let lineup: Vec<JokerCard> = jokers.iter().chain([offer]).copied().collect();
+            score_with(r, &lineup)
+        });
+        let scored = match (with, current) {
+            (Some(with), Some(current)) => format!("  score {with} ({:+})", with - current),
+            _ => String::new(),
+        };
+        let line = format!(
+            "  {}. {:<24} ${:<3}{scored}",
+            i + 1,
+            offer.to_string(),
+            cost(offer)
+        );
+        println!("{}", line.trim_end());
+    }
+    let owned: Vec<String> = jokers
+        .iter()
+        .enumerate()
+        .map(|(i, j)| format!("{}:{j} (sells ${})", i + 1, sell_value(j)))
+        .collect();
+    println!("jokers: {}", owned.join(", "));
+}
+
+pub fn run(args: ShopArgs) -> Result<(), Box<dyn Error>> {
+    let round = args.round.as_deref().map(crate::parse_round).transpose()?;
+    let mut jokers: Vec<JokerCard> = round.iter().flat_map(|r| r.jokers.clone()).collect();
+    jokers.extend(&args.jokers);
+    let mut money = args.money;
+    let config = ShopConfig {
+        slots: args.slots,
+        ..ShopConfig::default()
+    };
+    let seed = args.seed.unwrap_or_else(Seed::random);
+    println!("seed: {seed}");
+    let mut shop = Shop::new(config, &jokers, seed.rng(Stream::Shop));
+    show(&shop, &jokers, money, round.as_ref());
+
+    let script = match &args.script {
+        Some(path) => fs::read_to_string(path)?,
+        None => return Ok(()),
+    };
+    for (n, line) in script.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&action, rest)) = words.split_first() else {
+            continue;
+        };
+        let number = || match rest {
+            [n] => n
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n >= 1)
+                .map(|n| n - 1)
+                .ok_or_else(|| format!("`{n}` is not a number from 1")),
+            _ => Err(format!("{action} needs one number")),
+        };
+        let result = match action {
+            "buy" => number().and_then(|i| {
+                let card = shop.buy(i, &mut jokers, args.joker_slots, &mut money)?;
+                Ok(format!("bought {card} for ${}", cost(&card)))
+            }),
+            "sell" => number().and_then(|i| {
+                let card = sell(&mut jokers, i, &mut money)?;
+                Ok(format!("sold {card} for ${}", sell_value(&card)))
+            }),
+            "reroll" => shop
+                .reroll(&jokers, &mut money)
+                .map(|price| format!("rerolled for ${price}")),
+            _ => Err(format!("unknown action `{action}`")),
+        };
+        println!("{}", result.map_err(|e| format!("line {}: {e}", n + 1))?);
+        show(&shop, &jokers, money, round.as_ref());
+    }
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::error::Error;
+
+use clap::{Args, ValueEnum};
+use ortalab::models::ante::Stake;
+use ortalab::models::deck::DeckKind;
+use ortalab::models::run::{RunConfig, RunReport, Strategy, WINNING_ANTE, simulate};
+use ortalab::models::seed::{Seed, Stream};
+use ortalab::models::strategy::{Greedy, Random};
+use serde_json::{Value, json};
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StrategyKind {
+    /// Play the best hand, buy the dearest jokers
+    Greedy,
+    /// Pick uniformly among allowed actions
+    Random,
+}
+
+#[derive(Args)]
+pub struct SimulateArgs {
+    #[arg(long, value_enum, default_value_t = StrategyKind::Greedy)]
+    strategy: StrategyKind,
+
+    /// Starting deck (standard, abandoned, checkered, erratic, painted)
+    #[arg(long, default_value = "standard")]
+    deck: DeckKind,
+
+    /// Stake (white, red, green, black, blue, purple, orange, gold)
+    #[arg(long, default_value = "white")]
+    stake: Stake,
+
+    /// 8-character seed, e.g. 7LB2WVPK; a random one is picked and
+    /// printed when omitted
+    #[arg(long)]
+    seed: Option<Seed>,
+
+    /// Keep playing past ante 8 until a blind is lost
+    #[arg(long)]
+    endless: bool,
+
+    /// Simulate this many runs with consecutive seeds and print a summary
+    /// line for each plus averages, instead of one run's log
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    runs: u64,
+
+    /// Print the run log and summary as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn summary(report: &RunReport) -> String {
+    let result = if report.won {
+        "won".to_string()
+    } else {
+        match report.blinds.last() {
+            Some(blind) => format!("lost at Ante {} {}", blind.ante, blind.blind),
+            None => "lost".to_string(),
+        }
+    };
+    format!(
+        "seed {}: {result}, furthest ante {}, {} blinds won, final score {}, best hand {}, ${} left",
+        report.config.seed,
+        report.furthest_ante(),
+        report.blinds_won(),
+        report.final_score(),
+        report.best_hand,
+        report.money_curve.last().copied().unwrap_or_default()
+    )
+}
+
+fn to_json(report: &RunReport) -> Value {
+    let blinds: Vec<Value> = report
+        .blinds
+        .iter()
+        .map(|b| {
+            json!({
+                "ante": b.ante,
+                "blind": b.blind.to_string(),
+                "target": b.target,
+                "score": b.score,
+                "won": b.won,
+            })
+        })
+        .collect();
+    json!({
+        "strategy": report.strategy,
+        "seed": report.config.seed.to_string(),
+        "won": report.won,
+        "furthest_ante": report.furthest_ante(),
+        "blinds_won": report.blinds_won(),
+        "final_score": report.final_score(),
+        "total_score": report.total_score(),
+        "best_hand": report.best_hand,
+        "money_curve": report.money_curve,
+        "blinds": blinds,
+        "log": report.log,
+    })
+}
+
+pub fn run(args: SimulateArgs) -> Result<(), Box<dyn Error>> {
+    let mut reports = Vec::new();
+    let first = args.seed.unwrap_or_else(Seed::random);
+    for n in 0..args.runs {
+        let seed = Seed::from_u64(first.to_u64() + n);
+        let config = RunConfig {
+            deck: args.deck,
+            stake: args.stake,
+            seed,
+            last_ante: (!args.endless).then_some(WINNING_ANTE),
+        };
+        let mut strategy: Box<dyn Strategy> = match args.strategy {
+            StrategyKind::Greedy => Box::new(Greedy),
+            StrategyKind::Random => Box::new(Random::new(seed.stream_seed(Stream::Strategy))),
+        };
+        reports.push(simulate(&config, strategy.as_mut())?);
+    }
+
+    if args.json {
+        let runs: Vec<Value> = reports.iter().map(to_json).collect();
+        let out = match runs.as_slice() {
+            [run] => run.clone(),
+            _ => Value::Array(runs),
+        };
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+    if let [report] = reports.as_slice() {
+        for line in &report.log {
+            println!("{line}");
+        }
+        println!("{}", summary(report));
+        let curve: Vec<String> = report.money_curve.iter().map(|m| format!("${m}")).collect();
+        println!("money: {}", curve.join(" "));
+        return Ok(());
+    }
+
+    for report in &reports {
+        println!("{}", summary(report));
+    }
+    let n = reports.len() as f64;
+    let wins = reports.iter().filter(|r| r.won).count();
+    let antes: f64 = reports.iter().map(|r| f64::from(r.furthest_ante())).sum();
+    println!(
+        "{wins}/{} won, mean furthest ante {:.2}",
+        reports.len(),
+        antes / n
+    );
+    Ok(())
+}
@@ -0,0 +1,461 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use enum_iterator::{Sequence, next_cycle, previous_cycle};
+use ortalab::models::session::{Session, Zone};
+use ortalab::models::solver::MAX_PLAYED;
+use ortalib::{Card, Edition, Joker, JokerCard, Rank, Round, Suit};
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+
+#[derive(Args)]
+pub struct TuiArgs {
+    /// Round to start from; starts empty if omitted
+    file: Option<PathBuf>,
+}
+
+const CARD_WIDTH: u16 = 9;
+const JOKER_WIDTH: u16 = 18;
+
+const KEYS: &str = "tab: zone  ←→: select  a: add  x: delete  m: played/held  r/s: rank/suit  \
+e: enhancement  d: edition  j: joker type  </>: move joker  u: undo  q: quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Played,
+    Held,
+    Jokers,
+}
+
+impl Focus {
+    fn next(self) -> Self {
+        match self {
+            Focus::Played => Focus::Held,
+            Focus::Held => Focus::Jokers,
+            Focus::Jokers => Focus::Played,
+        }
+    }
+
+    fn zone(self) -> Option<Zone> {
+        match self {
+            Focus::Played => Some(Zone::Played),
+            Focus::Held => Some(Zone::Held),
+            Focus::Jokers => None,
+        }
+    }
+}
+
+struct App {
+    session: Session,
+    focus: Focus,
+    cursor: usize,
+    message: String,
+}
+
+fn cycle<T: Sequence>(value: &T, forward: bool) -> T {
+    if forward {
+        next_cycle(value)
+    } else {
+        previous_cycle(value)
+    }
+}
+
+fn suit_color(suit: Suit) -> Color {
+    match suit {
+        Suit::Spades => Color::LightBlue,
+        Suit::Hearts => Color::LightRed,
+        Suit::Clubs => Color::LightGreen,
+        Suit::Diamonds => Color::Yellow,
+    }
+}
+
+fn edition_color(edition: Option<Edition>) -> Color {
+    match edition {
+        Some(Edition::Foil) => Color::Cyan,
+        Some(Edition::Holographic) => Color::Magenta,
+        Some(Edition::Polychrome) => Color::LightYellow,
+        None => Color::DarkGray,
+    }
+}
+
+fn badge<T: ToString>(value: Option<T>) -> String {
+    value.map_or(String::new(), |v| v.to_string())
+}
+
+impl App {
+    fn len(&self) -> usize {
+        let round = &self.session.round;
+        match self.focus {
+            Focus::Played => round.cards_played.len(),
+            Focus::Held => round.cards_held_in_hand.len(),
+            Focus::Jokers => round.jokers.len(),
+        }
+    }
+
+    fn clamp_cursor(&mut self) {
+        self.cursor = self.cursor.min(self.len().saturating_sub(1));
+    }
+
+    fn selected_card(&self) -> Option<(Zone, Card)> {
+        let zone = self.focus.zone()?;
+        let cards = match zone {
+            Zone::Played => &self.session.round.cards_played,
+            Zone::Held => &self.session.round.cards_held_in_hand,
+        };
+        cards.get(self.cursor).map(|card| (zone, *card))
+    }
+
+    fn selected_joker(&self) -> Option<JokerCard> {
+        match self.focus {
+            Focus::Jokers => self.session.round.jokers.get(self.cursor).copied(),
+            _ => None,
+        }
+    }
+
+    fn edit_card(&mut self, edit: impl FnOnce(Card) -> Card) -> Result<(), String> {
+        let (zone, card) = self.selected_card().ok_or("no card selected")?;
+        self.session.set_card(zone, self.cursor, edit(card))
+    }
+
+    fn edit_joker(&mut self, edit: impl FnOnce(JokerCard) -> JokerCard) -> Result<(), String> {
+        let joker = self.selected_joker().ok_or("no joker selected")?;
+        self.session.set_joker(self.cursor, edit(joker))
+    }
+
+    /// Applies one key press; returns `false` when the user asked to quit.
+    fn handle_key(&mut self, key: KeyCode) -> bool {
+        let result = match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Tab => {
+                self.focus = self.focus.next();
+                Ok(())
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.cursor = self.cursor.saturating_sub(1);
+                Ok(())
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.cursor += 1;
+                Ok(())
+            }
+            KeyCode::Char('a') => {
+                let added = match self.focus.zone() {
+                    Some(zone) => {
+                        let card = Card::new(Rank::Ace, Suit::Spades, None, None);
+                        self.session.add_cards(zone, &[card])
+                    }
+                    None => {
+                        self.session.add_joker(JokerCard::new(Joker::Joker, None));
+                        Ok(())
+                    }
+                };
+                added.map(|()| self.cursor = self.len() - 1)
+            }
+            KeyCode::Char('x') | KeyCode::Delete => match self.focus.zone() {
+                Some(zone) => self.session.remove_card(zone, self.cursor).map(|_| ()),
+                None => self.session.remove_joker(self.cursor).map(|_| ()),
+            },
+            KeyCode::Char('m') => match self.focus.zone() {
+                Some(zone) => self.session.move_card(zone, self.cursor),
+                None => Err("jokers stay in their slots; use < and > to reorder".to_string()),
+            },
+            KeyCode::Char(c @ ('r' | 'R')) => self.edit_card(|card| {
+                Card::new(
+                    cycle(&card.rank, c == 'r'),
+                    card.suit,
+                    card.enhancement,
+                    card.edition,
+                )
+            }),
+            KeyCode::Char(c @ ('s' | 'S')) => self.edit_card(|card| {
+                Card::new(
+                    card.rank,
+                    cycle(&card.suit, c == 's'),
+                    card.enhancement,
+                    card.edition,
+                )
+            }),
+            KeyCode::Char(c @ ('e' | 'E')) => self.edit_card(|card| {
+                let enhancement = cycle(&card.enhancement, c == 'e');
+                Card::new(card.rank, card.suit, enhancement, card.edition)
+            }),
+            KeyCode::Char(c @ ('d' | 'D')) => match self.focus.zone() {
+                Some(_) => self.edit_card(|card| {
+                    let edition = cycle(&card.edition, c == 'd');
+                    Card::new(card.rank, card.suit, card.enhancement, edition)
+                }),
+                None => self.edit_joker(|joker| {
+                    JokerCard::new(joker.joker, cycle(&joker.edition, c == 'd'))
+                }),
+            },
+            KeyCode::Char(c @ ('j' | 'J')) => self
+                .edit_joker(|joker| JokerCard::new(cycle(&joker.joker, c == 'j'), joker.edition)),
+            KeyCode::Char(c @ ('<' | '>')) => {
+                let target = if c == '<' {
+                    self.cursor.checked_sub(1)
+                } else {
+                    Some(self.cursor + 1)
+                };
+                match (self.focus, target) {
+                    (Focus::Jokers, Some(to)) => self
+                        .session
+                        .move_joker(self.cursor, to)
+                        .map(|()| self.cursor = to),
+                    (Focus::Jokers, None) => Ok(()),
+                    _ => Err("select a joker to reorder".to_string()),
+                }
+            }
+            KeyCode::Char('u') => {
+                if self.session.undo() {
+                    Ok(())
+                } else {
+                    Err("nothing to undo".to_string())
+                }
+            }
+            _ => Ok(()),
+        };
+        self.message = result.err().unwrap_or_default();
+        self.clamp_cursor();
+        true
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(4)]).areas(frame.area());
+        let [board, side] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(44)]).areas(main);
+        let [played, held, jokers] = Layout::vertical([
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(6),
+        ])
+        .areas(board);
+
+        let round = &self.session.round;
+        self.draw_cards(frame, played, "Played", Focus::Played, &round.cards_played);
+        self.draw_cards(frame, held, "Held", Focus::Held, &round.cards_held_in_hand);
+        self.draw_jokers(frame, jokers, &round.jokers);
+        self.draw_breakdown(frame, side);
+
+        // The message goes first so a narrow terminal wrapping the key list
+        // cannot push it out of the footer.
+        let mut footer_text = vec![Line::from(KEYS).style(Style::new().fg(Color::Gray))];
+        if !self.message.is_empty() {
+            let message = Line::from(self.message.as_str()).style(Style::new().fg(Color::LightRed));
+            footer_text.insert(0, message);
+        }
+        frame.render_widget(
+            Paragraph::new(footer_text)
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered()),
+            footer,
+        );
+    }
+
+    fn zone_block(&self, title: &str, focus: Focus) -> Block<'static> {
+        let style = if self.focus == focus {
+            Style::new().fg(Color::White).add_modifier(Modifier::BOLD)
+        } else {
+            Style::new().fg(Color::DarkGray)
+        };
+        Block::bordered()
+            .title(format!(" {title} "))
+            .border_style(style)
+    }
+
+    fn tile_block(&self, focus: Focus, index: usize, color: Color) -> Block<'static> {
+        let mut block = Block::bordered().border_style(Style::new().fg(color));
+        if self.focus == focus && self.cursor == index {
+            block = block
+                .border_type(ratatui::widgets::BorderType::Thick)
+                .border_style(Style::new().fg(Color::White));
+        }
+        block
+    }
+
+    fn tiles(area: Rect, count: usize, width: u16) -> Vec<Rect> {
+        let constraints = vec![Constraint::Length(width); count];
+        Layout::horizontal(constraints).split(area).to_vec()
+    }
+
+    fn draw_cards(&self, frame: &mut Frame, area: Rect, title: &str, focus: Focus, cards: &[Card]) {
+        let block = self.zone_block(title, focus);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        for (i, (card, tile)) in cards
+            .iter()
+            .zip(Self::tiles(inner, cards.len(), CARD_WIDTH))
+            .enumerate()
+        {
+            let text = vec![
+                Line::from(Span::styled(
+                    format!("{}{}", card.rank, card.suit),
+                    Style::new()
+                        .fg(suit_color(card.suit))
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(badge(card.enhancement)),
+                Line::from(badge(card.edition)).style(Style::new().fg(edition_color(card.edition))),
+            ];
+            let block = self.tile_block(focus, i, edition_color(card.edition));
+            frame.render_widget(Paragraph::new(text).block(block), tile);
+        }
+    }
+
+    fn draw_jokers(&self, frame: &mut Frame, area: Rect, jokers: &[JokerCard]) {
+        let block = self.zone_block("Jokers", Focus::Jokers);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        for (i, (joker, tile)) in jokers
+            .iter()
+            .zip(Self::tiles(inner, jokers.len(), JOKER_WIDTH))
+            .enumerate()
+        {
+            let text = vec![
+                Line::from(Span::styled(
+                    joker.joker.to_string(),
+                    Style::new().add_modifier(Modifier::BOLD),
+                )),
+                Line::from(format!("#{}", i + 1)),
+                Line::from(badge(joker.edition))
+                    .style(Style::new().fg(edition_color(joker.edition))),
+            ];
+            let block = self.tile_block(Focus::Jokers, i, edition_color(joker.edition));
+            frame.render_widget(
+                Paragraph::new(text).wrap(Wrap { trim: true }).block(block),
+                tile,
+            );
+        }
+    }
+
+    fn draw_breakdown(&self, frame: &mut Frame, area: Rect) {
+        let lines = match self.session.evaluate() {
+            Ok(Some(sorce)) => {
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        sorce.hand.to_string(),
+                        Style::new().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(format!(
+                        "{} Chips x {} Mult",
+                        sorce.card_chips, sorce.base_mult
+                    )),
+                    Line::from(""),
+                ];
+                lines.extend(sorce.steps.iter().map(|step| Line::from(step.to_string())));
+                lines.push(Line::from(""));
+                lines.push(Line::from(format!(
+                    "= {} Chips x {} Mult",
+                    sorce.total_chips, sorce.mult
+                )));
+                lines.push(Line::from(Span::styled(
+                    format!("Score: {}", (sorce.total_chips * sorce.mult).floor()),
+                    Style::new()
+                        .fg(Color::LightGreen)
+                        .add_modifier(Modifier::BOLD),
+                )));
+                lines
+            }
+            Ok(None) => vec![Line::from("no cards played")],
+            Err(err) => vec![Line::from(Span::styled(
+                format!("error: {err}"),
+                Style::new().fg(Color::LightRed),
+            ))],
+        };
+        let block = Block::bordered().title(" Breakdown ");
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), Box<dyn Error>> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && !app.handle_key(key.code)
+        {
+            return Ok(());
+        }
+    }
+}
+
+pub fn run(args: TuiArgs) -> Result<(), Box<dyn Error>> {
+    let round = match &args.file {
+        Some(file) => crate::parse_round(file)?,
+        None => Round {
+            cards_played: Vec::new(),
+            cards_held_in_hand: Vec::new(),
+            jokers: Vec::new(),
+        },
+    };
+    // Editing keeps the played cards within the game's limit, so a file
+    // over it is refused up front rather than opened in a state the keys
+    // could never reach.
+    if round.cards_played.len() > MAX_PLAYED {
+        return Err(format!(
+            "{} cards played, at most {MAX_PLAYED} may be",
+            round.cards_played.len()
+        )
+        .into());
+    }
+    let mut app = App {
+        session: Session::new(round),
+        focus: Focus::Played,
+        cursor: 0,
+        message: String::new(),
+    };
+    ratatui::run(|terminal| event_loop(terminal, &mut app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(played: usize) -> App {
+        let card = Card::new(Rank::Ace, Suit::Spades, None, None);
+        App {
+            session: Session::new(Round {
+                cards_played: vec![card; played],
+                cards_held_in_hand: vec![card],
+                jokers: Vec::new(),
+            }),
+            focus: Focus::Played,
+            cursor: 0,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn adding_past_the_limit_shows_a_message() {
+        let mut app = app(5);
+        assert!(app.handle_key(KeyCode::Char('a')));
+        assert_eq!(app.message, "at most 5 cards may be played (5 already are)");
+        assert_eq!(app.session.round.cards_played.len(), 5);
+    }
+
+    #[test]
+    fn moving_into_a_full_hand_shows_a_message() {
+        let mut app = app(5);
+        app.handle_key(KeyCode::Tab);
+        app.handle_key(KeyCode::Char('m'));
+        assert!(!app.message.is_empty());
+        assert_eq!(app.session.round.cards_held_in_hand.len(), 1);
+    }
+
+    #[test]
+    fn removing_every_played_card_leaves_no_breakdown() {
+        let mut app = app(1);
+        app.handle_key(KeyCode::Char('x'));
+        assert!(app.message.is_empty());
+        assert!(app.session.round.cards_played.is_empty());
+        assert!(matches!(app.session.evaluate(), Ok(None)));
+    }
+}
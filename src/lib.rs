@@ -0,0 +1,30 @@
+pub mod models;
+
+use crate::models::jokers::HandJoker;
+use crate::models::pokerhand::HandValue;
+use crate::models::rng::SeededRng;
+use crate::models::sorce::Sorce;
+use ortalib::{Chips, Mult, Round};
+
+/// Runs a round through `HandValue`, `HandJoker` and `Sorce`, keeping the
+/// full breakdown.
+pub fn evaluate(round: Round) -> Sorce {
+    evaluate_seeded(round, None)
+}
+
+/// Like [`evaluate`], also rolling chance-based effects such as Glass
+/// shattering on `rng`.
+pub fn evaluate_seeded(round: Round, rng: Option<&mut SeededRng>) -> Sorce {
+    let hand = HandValue::evaluation(
+        &round.cards_played,
+        &round.cards_held_in_hand,
+        &round.jokers,
+    );
+    let new_hand = HandJoker::analyze(&hand);
+    Sorce::get_card_seeded(new_hand, rng)
+}
+
+pub fn score(round: Round) -> (Chips, Mult) {
+    let sorce = evaluate(round);
+    (sorce.total_chips, sorce.mult)
+}
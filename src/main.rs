@@ -5,50 +5,228 @@ use std::{
     path::{Path, PathBuf},
 };
 
-mod models;
-use crate::models::jokers;
-use crate::models::pokerhand::HandValue;
-use crate::models::sorce::Sorce;
+mod commands;
+mod watch;
+use crate::commands::Command;
 use clap::Parser;
-use ortalib::{Chips, Mult, Round};
+use ortalab::evaluate_seeded;
+use ortalab::models::ante::{BlindKind, Stake, required_score};
+use ortalab::models::catalog;
+use ortalab::models::numbers::{BigScore, Exact, game_format};
+use ortalab::models::seed::{Seed, Stream};
+use ortalab::models::sorce::Sorce;
+use ortalib::Round;
+use serde::Deserialize;
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Opts {
-    file: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Round file to score; with `--watch`, any number of files or directories
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
 
     #[arg(long)]
     explain: bool,
+
+    /// Show the score as the game does: 12,345 / 1.234e11 / naneinf
+    #[arg(long)]
+    game_format: bool,
+
+    /// Score with arbitrary-magnitude numbers that cannot overflow
+    #[arg(long, conflicts_with = "exact")]
+    big: bool,
+
+    /// Score with exact rational numbers and flag any f64 rounding error
+    #[arg(long, conflicts_with = "game_format")]
+    exact: bool,
+
+    /// Refuse rounds with jokers the engine does not implement
+    #[arg(long)]
+    strict: bool,
+
+    /// Roll chance-based effects (Glass shattering) with this 8-character
+    /// seed, e.g. 7LB2WVPK, and report the destroyed cards
+    #[arg(long)]
+    seed: Option<Seed>,
+
+    /// Report the probability of each chance-based outcome
+    #[arg(long)]
+    expected: bool,
+
+    /// Keep running and rescore rounds whenever the files change
+    #[arg(long)]
+    watch: bool,
+
+    /// Report which blinds of this ante the score beats
+    #[arg(long)]
+    ante: Option<u32>,
+
+    /// Stake for `--ante`, default white (white, red, green, black, blue, purple, orange, gold)
+    #[arg(long, requires = "ante")]
+    stake: Option<Stake>,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let opts = Opts::parse();
-    let round = parse_round(&opts)?;
+    let mut opts = Opts::parse();
+    if let Some(command) = opts.command.take() {
+        return commands::run(command);
+    }
+
+    if opts.watch {
+        return watch::watch(&opts.files, |file| report(parse_round(file)?, &opts));
+    }
+    let [file] = opts.files.as_slice() else {
+        return Err("scoring several files needs --watch".into());
+    };
+    report(parse_round(file)?, &opts)?;
+    Ok(())
+}
+
+/// Scores one round and prints everything the options ask for, returning
+/// the score so watch mode can diff it.
+fn report(round: Round, opts: &Opts) -> Result<f64, Box<dyn Error>> {
+    check_jokers(&round, opts.strict)?;
 
-    let (chips, mult) = score(round);
+    let mut rng = opts.seed.map(|seed| seed.rng(Stream::Glass));
+    let sorce = evaluate_seeded(round, rng.as_mut());
+    if opts.explain {
+        print_explain(&sorce);
+    }
 
-    println!("{}", (chips * mult).floor());
+    let (chips, mult) = (sorce.total_chips, sorce.mult);
+    if opts.exact {
+        let (exact_chips, exact_mult) = sorce.replay::<Exact>();
+        let total = exact_chips * exact_mult;
+        println!("{}", total.floor());
+        eprintln!("exact: {total}");
+        if !total.floor_matches(chips * mult) {
+            eprintln!(
+                "warning: f64 scoring gives {}, which floors differently",
+                (chips * mult).floor()
+            );
+        }
+    } else if opts.big {
+        let (chips, mult) = sorce.replay::<BigScore>();
+        let total = (chips * mult).floor();
+        if opts.game_format {
+            println!("{}", total.game_format());
+        } else {
+            println!("{total}");
+        }
+        if total.is_approximate() {
+            eprintln!("precision: lost, digits past the 16th are approximate");
+        } else {
+            eprintln!("precision: exact");
+        }
+    } else if opts.game_format {
+        println!("{}", game_format((chips * mult).floor()));
+    } else {
+        println!("{}", (chips * mult).floor());
+    }
+    if let Some(ante) = opts.ante {
+        print_blinds(
+            (chips * mult).floor(),
+            ante,
+            opts.stake.unwrap_or(Stake::White),
+        );
+    }
+    if let Some(seed) = opts.seed {
+        println!("seed: {seed}");
+        print_destroyed(&sorce);
+    }
+    if opts.expected {
+        print_expected(&sorce);
+    }
+    Ok((chips * mult).floor())
+}
+
+/// Warns about (or with `strict`, rejects) jokers whose effects the engine
+/// would silently skip.
+fn check_jokers(round: &Round, strict: bool) -> Result<(), Box<dyn Error>> {
+    let missing = catalog::unimplemented(&round.jokers);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<String> = missing.iter().map(|j| j.to_string()).collect();
+    let names = names.join(", ");
+    if strict {
+        return Err(format!("unimplemented jokers: {names}").into());
+    }
+    eprintln!("warning: unimplemented jokers are ignored, score may be wrong: {names}");
     Ok(())
 }
 
-fn parse_round(opts: &Opts) -> Result<Round, Box<dyn Error>> {
+fn print_blinds(score: f64, ante: u32, stake: Stake) {
+    for blind in BlindKind::ALL {
+        let required = required_score(ante, blind, stake);
+        let verdict = if score >= required {
+            "beats"
+        } else {
+            "falls short of"
+        };
+        println!("{verdict} {blind} of Ante {ante} at {stake} (needs {required})");
+    }
+}
+
+fn print_destroyed(sorce: &Sorce) {
+    if sorce.destroyed.is_empty() {
+        println!("destroyed: none");
+    } else {
+        let cards: Vec<String> = sorce.destroyed.iter().map(|c| c.to_string()).collect();
+        println!("destroyed: {}", cards.join(", "));
+    }
+}
+
+/// Probability-weighted outcomes of every chance-based effect. None of them
+/// change this hand's score, only which cards survive it.
+fn print_expected(sorce: &Sorce) {
+    let mut none_destroyed = 1.0;
+    let mut expected_destroyed = 0.0;
+    for (card, chance) in &sorce.at_risk {
+        println!("{card}: destroyed with probability {chance}");
+        none_destroyed *= 1.0 - chance;
+        expected_destroyed += chance;
+    }
+    println!("expected cards destroyed: {expected_destroyed}");
+    println!("probability no card is destroyed: {none_destroyed}");
+}
+
+pub(crate) fn print_explain(sorce: &Sorce) {
+    println!(
+        "{} ({} Chips x {} Mult)",
+        sorce.hand, sorce.card_chips, sorce.base_mult
+    );
+    for step in &sorce.steps {
+        println!("  {step}");
+    }
+    println!("= {} Chips x {} Mult", sorce.total_chips, sorce.mult);
+}
+
+pub(crate) fn read_input(file: &Path) -> Result<String, Box<dyn Error>> {
     let mut input = String::new();
-    if opts.file == Path::new("-") {
+    if file == Path::new("-") {
         stdin().read_to_string(&mut input)?;
     } else {
-        File::open(&opts.file)?.read_to_string(&mut input)?;
+        File::open(file)?.read_to_string(&mut input)?;
     }
+    Ok(input)
+}
 
+fn parse_round(file: &Path) -> Result<Round, Box<dyn Error>> {
+    let input = read_input(file)?;
     let round = serde_yaml::from_str(&input)?;
     Ok(round)
 }
 
-fn score(round: Round) -> (Chips, Mult) {
-    let hand = HandValue::evaluation(
-        &round.cards_played,
-        &round.cards_held_in_hand,
-        &round.jokers,
-    );
-    let new_hand = jokers::HandJoker::analyze(&hand);
-    let sorce = Sorce::get_card(new_hand);
-    return (sorce.total_chips, sorce.mult);
+/// Every round in a file, which may hold several `---` separated documents.
+fn parse_rounds(file: &Path) -> Result<Vec<Round>, Box<dyn Error>> {
+    let input = read_input(file)?;
+    let mut rounds = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(&input) {
+        rounds.push(Round::deserialize(document)?);
+    }
+    Ok(rounds)
 }
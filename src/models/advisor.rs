@@ -0,0 +1,234 @@
+use super::rng::SeededRng;
+use super::solver::{self, MAX_PLAYED};
+use ortalib::{Card, Edition, Enhancement, JokerCard, Rank, Round, Suit};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdvisorConfig {
+    /// Largest discard to consider.
+    pub max_discard: usize,
+    /// Redraws are enumerated exactly when there are at most this many.
+    pub exact_limit: usize,
+    /// Sampled redraws per choice when there are more.
+    pub samples: usize,
+    pub target: Option<f64>,
+}
+
+impl Default for AdvisorConfig {
+    fn default() -> Self {
+        Self {
+            max_discard: MAX_PLAYED,
+            exact_limit: 64,
+            samples: 32,
+            target: None,
+        }
+    }
+}
+
+/// One discard choice and what the hand is worth after redrawing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscardOption {
+    pub discard: Vec<Card>,
+    /// Mean best score over the redraws.
+    pub expected: f64,
+    /// Share of redraws whose best score reaches the target.
+    pub hit_chance: Option<f64>,
+    /// How many redraws were scored.
+    pub outcomes: usize,
+    /// Whether every possible redraw was scored rather than a sample.
+    pub exact: bool,
+}
+
+type CardKey = (Rank, Suit, Option<Enhancement>, Option<Edition>);
+
+/// Best scores keyed by hand contents, since many redraws produce the same
+/// hand. Hands are solved in sorted order so the key fully determines the
+/// result.
+struct BestScores<'a> {
+    jokers: &'a [JokerCard],
+    scores: HashMap<Vec<CardKey>, f64>,
+}
+
+impl BestScores<'_> {
+    fn best(&mut self, mut hand: Vec<Card>) -> f64 {
+        if hand.is_empty() {
+            return 0.0;
+        }
+        hand.sort_by_key(|c| (c.rank, c.suit, c.enhancement, c.edition));
+        let key = hand
+            .iter()
+            .map(|c| (c.rank, c.suit, c.enhancement, c.edition))
+            .collect();
+        let jokers = self.jokers;
+        *self.scores.entry(key).or_insert_with(|| {
+            let round = Round {
+                cards_played: hand,
+                cards_held_in_hand: Vec::new(),
+                jokers: jokers.to_vec(),
+            };
+            solver::solve(&round).map_or(0.0, |s| s.score)
+        })
+    }
+}
+
+/// Every way to choose `k` of `0..n`, in lexicographic order.
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut all = Vec::new();
+    let mut current: Vec<usize> = (0..k).collect();
+    if k > n {
+        return all;
+    }
+    loop {
+        all.push(current.clone());
+        let Some(i) = (0..k).rev().find(|&i| current[i] < n - k + i) else {
+            return all;
+        };
+        current[i] += 1;
+        for j in i + 1..k {
+            current[j] = current[j - 1] + 1;
+        }
+    }
+}
+
+/// `n` choose `k`, saturating rather than overflowing.
+fn choose(n: usize, k: usize) -> usize {
+    (0..k.min(n))
+        .try_fold(1usize, |acc, i| acc.checked_mul(n - i).map(|p| p / (i + 1)))
+        .unwrap_or(usize::MAX)
+}
+
+/// Scores every discard of up to `max_discard` cards from `hand`, including
+/// discarding nothing, by the best play available after redrawing the same
+/// number of cards from `deck`. Sorted by expected score, best first.
+pub fn advise(
+    hand: &[Card],
+    deck: &[Card],
+    jokers: &[JokerCard],
+    config: &AdvisorConfig,
+    rng: &mut SeededRng,
+) -> Vec<DiscardOption> {
+    let mut best = BestScores {
+        jokers,
+        scores: HashMap::new(),
+    };
+    // Every choice sees the same sampled deck orders, so differences
+    // between choices are not just sampling noise.
+    let orders: Vec<Vec<Card>> = (0..config.samples)
+        .map(|_| {
+            let mut order = deck.to_vec();
+            rng.shuffle(&mut order);
+            order
+        })
+        .collect();
+
+    let mut options = Vec::new();
+    for size in 0..=config.max_discard.min(hand.len()) {
+        let draw = size.min(deck.len());
+        let exact = choose(deck.len(), draw) <= config.exact_limit;
+        let draws: Vec<Vec<Card>> = if exact {
+            combinations(deck.len(), draw)
+                .into_iter()
+                .map(|picked| picked.into_iter().map(|i| deck[i]).collect())
+                .collect()
+        } else {
+            orders.iter().map(|order| order[..draw].to_vec()).collect()
+        };
+
+        for discarded in combinations(hand.len(), size) {
+            let kept: Vec<Card> = (0..hand.len())
+                .filter(|i| !discarded.contains(i))
+                .map(|i| hand[i])
+                .collect();
+            let scores: Vec<f64> = draws
+                .iter()
+                .map(|drawn| best.best(kept.iter().chain(drawn).copied().collect()))
+                .collect();
+            let outcomes = scores.len();
+            options.push(DiscardOption {
+                discard: discarded.iter().map(|&i| hand[i]).collect(),
+                expected: scores.iter().sum::<f64>() / outcomes as f64,
+                hit_chance: config.target.map(|target| {
+                    scores.iter().filter(|&&s| s >= target).count() as f64 / outcomes as f64
+                }),
+                outcomes,
+                exact,
+            });
+        }
+    }
+    options.sort_by(|a, b| b.expected.total_cmp(&a.expected));
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(names: &[&str]) -> Vec<Card> {
+        names.iter().map(|name| name.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn combinations_are_lexicographic() {
+        let pairs = combinations(4, 2);
+        assert_eq!(pairs.len(), choose(4, 2));
+        assert_eq!(pairs[0], vec![0, 1]);
+        assert_eq!(pairs[5], vec![2, 3]);
+        assert_eq!(combinations(3, 0), vec![Vec::<usize>::new()]);
+        assert!(combinations(2, 3).is_empty());
+        assert_eq!(choose(52, 5), 2_598_960);
+        assert_eq!(choose(1000, 500), usize::MAX);
+    }
+
+    #[test]
+    fn small_decks_are_enumerated_exactly() {
+        let config = AdvisorConfig {
+            max_discard: 1,
+            target: Some(64.0),
+            ..AdvisorConfig::default()
+        };
+        let hand = cards(&["A♠", "2♣"]);
+        let deck = cards(&["A♥", "3♦"]);
+        let options = advise(&hand, &deck, &[], &config, &mut SeededRng::new(1));
+        let summary: Vec<(Vec<Rank>, f64, Option<f64>)> = options
+            .iter()
+            .map(|o| {
+                assert!(o.exact);
+                assert_eq!(o.outcomes, if o.discard.is_empty() { 1 } else { 2 });
+                let ranks = o.discard.iter().map(|c| c.rank).collect();
+                (ranks, o.expected, o.hit_chance)
+            })
+            .collect();
+        // Discarding the 2 draws a pair of Aces (64) or a 3 (16 for the
+        // Ace high); discarding the Ace leaves 2 or A high (8 or 16).
+        assert_eq!(
+            summary,
+            vec![
+                (vec![Rank::Two], 40.0, Some(0.5)),
+                (vec![], 16.0, Some(0.0)),
+                (vec![Rank::Ace], 12.0, Some(0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn large_decks_are_sampled_reproducibly() {
+        let config = AdvisorConfig {
+            max_discard: 2,
+            exact_limit: 0,
+            samples: 8,
+            target: None,
+        };
+        let hand = cards(&["A♠", "K♣", "7♦"]);
+        let deck = cards(&["A♥", "K♦", "Q♠", "J♣", "10♥", "2♠"]);
+        let run = |seed| advise(&hand, &deck, &[], &config, &mut SeededRng::new(seed));
+        let options = run(9);
+        assert_eq!(options.len(), 1 + 3 + 3);
+        for option in &options {
+            assert!(!option.exact || option.discard.is_empty());
+            if !option.discard.is_empty() {
+                assert_eq!(option.outcomes, 8);
+            }
+        }
+        assert_eq!(options, run(9));
+    }
+}
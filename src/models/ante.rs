@@ -0,0 +1,206 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The three blinds of every ante, in the order they are played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlindKind {
+    Small,
+    Big,
+    Boss,
+}
+
+impl BlindKind {
+    pub const ALL: [BlindKind; 3] = [BlindKind::Small, BlindKind::Big, BlindKind::Boss];
+
+    /// Multiplier on the ante's base chips. Individual boss blinds such as
+    /// The Wall ask for more; this is the common x2.
+    pub fn multiplier(self) -> f64 {
+        match self {
+            BlindKind::Small => 1.0,
+            BlindKind::Big => 1.5,
+            BlindKind::Boss => 2.0,
+        }
+    }
+}
+
+/// Difficulty levels; each stake includes every modifier of the ones
+/// below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stake {
+    White,
+    /// Small Blind gives no reward money.
+    Red,
+    /// Required scores scale faster.
+    Green,
+    /// Shop jokers can be Eternal.
+    Black,
+    /// -1 discard.
+    Blue,
+    /// Required scores scale faster still.
+    Purple,
+    /// Shop jokers can be Perishable.
+    Orange,
+    /// Shop jokers can be Rental.
+    Gold,
+}
+
+impl Stake {
+    /// Which row of the base chip table applies: 1 below Green, 2 from
+    /// Green, 3 from Purple.
+    pub fn scaling(self) -> usize {
+        match self {
+            Stake::White | Stake::Red => 1,
+            Stake::Green | Stake::Black | Stake::Blue => 2,
+            Stake::Purple | Stake::Orange | Stake::Gold => 3,
+        }
+    }
+
+    pub fn discard_modifier(self) -> i32 {
+        if self >= Stake::Blue { -1 } else { 0 }
+    }
+
+    pub fn small_blind_reward(self) -> bool {
+        self < Stake::Red
+    }
+}
+
+/// Base chips for antes 1 to 8, one row per stake scaling level.
+#[rustfmt::skip]
+const BASE_CHIPS: [[f64; 8]; 3] = [
+    [300.0, 800.0, 2000.0, 5000.0, 11000.0, 20000.0, 35000.0, 50000.0],
+    [300.0, 900.0, 2600.0, 8000.0, 20000.0, 36000.0, 60000.0, 100000.0],
+    [300.0, 1000.0, 3200.0, 9000.0, 25000.0, 60000.0, 110000.0, 200000.0],
+];
+
+/// Base chips below ante 1, which only ante-lowering effects reach.
+const BASE_CHIPS_BEFORE_ANTE_1: f64 = 100.0;
+
+/// The Small Blind requirement for an ante. Past ante 8 the game grows the
+/// ante 8 value exponentially and keeps only its two leading digits.
+pub fn base_chips(ante: u32, stake: Stake) -> f64 {
+    let row = &BASE_CHIPS[stake.scaling() - 1];
+    match ante {
+        0 => BASE_CHIPS_BEFORE_ANTE_1,
+        1..=8 => row[ante as usize - 1],
+        _ => {
+            let c = f64::from(ante - 8);
+            let d = 1.0 + 0.2 * c;
+            let amount = (row[7] * (1.6 + (0.75 * c).powf(d)).powf(c)).floor();
+            let unit = 10f64.powf((amount.log10() - 1.0).floor());
+            amount - amount % unit
+        }
+    }
+}
+
+pub fn required_score(ante: u32, blind: BlindKind, stake: Stake) -> f64 {
+    (base_chips(ante, stake) * blind.multiplier()).floor()
+}
+
+impl Display for BlindKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BlindKind::Small => "Small Blind",
+            BlindKind::Big => "Big Blind",
+            BlindKind::Boss => "Boss Blind",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for BlindKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        match lower.strip_suffix(" blind").unwrap_or(&lower) {
+            "small" => Ok(BlindKind::Small),
+            "big" => Ok(BlindKind::Big),
+            "boss" => Ok(BlindKind::Boss),
+            _ => Err(format!("Invalid BlindKind: `{s}`")),
+        }
+    }
+}
+
+impl Display for Stake {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Stake::White => "White",
+            Stake::Red => "Red",
+            Stake::Green => "Green",
+            Stake::Black => "Black",
+            Stake::Blue => "Blue",
+            Stake::Purple => "Purple",
+            Stake::Orange => "Orange",
+            Stake::Gold => "Gold",
+        };
+        write!(f, "{name} Stake")
+    }
+}
+
+impl FromStr for Stake {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        match lower.strip_suffix(" stake").unwrap_or(&lower) {
+            "white" => Ok(Stake::White),
+            "red" => Ok(Stake::Red),
+            "green" => Ok(Stake::Green),
+            "black" => Ok(Stake::Black),
+            "blue" => Ok(Stake::Blue),
+            "purple" => Ok(Stake::Purple),
+            "orange" => Ok(Stake::Orange),
+            "gold" => Ok(Stake::Gold),
+            _ => Err(format!("Invalid Stake: `{s}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blinds_scale_the_ante_base() {
+        assert_eq!(required_score(1, BlindKind::Small, Stake::White), 300.0);
+        assert_eq!(required_score(2, BlindKind::Big, Stake::White), 1200.0);
+        assert_eq!(required_score(8, BlindKind::Boss, Stake::White), 100000.0);
+        assert_eq!(required_score(0, BlindKind::Small, Stake::White), 100.0);
+    }
+
+    #[test]
+    fn stakes_pick_their_row() {
+        assert_eq!(base_chips(3, Stake::Red), 2000.0);
+        assert_eq!(base_chips(3, Stake::Green), 2600.0);
+        assert_eq!(base_chips(3, Stake::Blue), 2600.0);
+        assert_eq!(base_chips(3, Stake::Purple), 3200.0);
+        assert_eq!(base_chips(8, Stake::Gold), 200000.0);
+    }
+
+    #[test]
+    fn antes_past_8_keep_two_leading_digits() {
+        assert_eq!(base_chips(9, Stake::White), 110000.0);
+        assert_eq!(base_chips(10, Stake::White), 560000.0);
+        assert_eq!(base_chips(11, Stake::White), 7200000.0);
+    }
+
+    #[test]
+    fn stake_modifiers_accumulate() {
+        assert!(Stake::White.small_blind_reward());
+        assert!(!Stake::Red.small_blind_reward());
+        assert_eq!(Stake::Black.discard_modifier(), 0);
+        assert_eq!(Stake::Gold.discard_modifier(), -1);
+    }
+
+    #[test]
+    fn names_parse_in_any_case_with_or_without_suffix() {
+        assert_eq!("BIG".parse(), Ok(BlindKind::Big));
+        assert_eq!("boss blind".parse(), Ok(BlindKind::Boss));
+        assert_eq!("Purple Stake".parse(), Ok(Stake::Purple));
+        assert_eq!(Stake::Gold.to_string().parse(), Ok(Stake::Gold));
+        assert_eq!(
+            "tiny".parse::<BlindKind>(),
+            Err("Invalid BlindKind: `tiny`".to_string())
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use super::rng::SeededRng;
+use super::round::clone_round;
+use ortalib::{JokerCard, Round};
+use std::collections::HashMap;
+
+/// Lineups up to this size get exact Shapley values from all `2^n` joker
+/// subsets; larger ones are estimated by sampling orderings.
+pub const EXACT_SHAPLEY_JOKERS: usize = 10;
+
+/// Joker subsets are bit masks in a `u64`; one bit is kept spare so the
+/// mask of a whole lineup, `(1 << n) - 1`, cannot overflow.
+pub const MAX_JOKERS: usize = u64::BITS as usize - 1;
+
+/// How the Shapley values were obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Exact,
+    Sampled(usize),
+}
+
+/// Unfloored chips x mult, so contributions add up exactly. A round with
+/// nothing played scores 0 rather than reaching the engine.
+fn value(round: Round) -> f64 {
+    if round.cards_played.is_empty() {
+        return 0.0;
+    }
+    let (chips, mult) = crate::score(round);
+    chips * mult
+}
+
+/// The round with only the jokers in `mask`, keeping their relative order.
+fn with_jokers(round: &Round, mask: u64) -> Round {
+    let jokers: Vec<JokerCard> = round
+        .jokers
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask & 1 << i != 0)
+        .map(|(_, j)| *j)
+        .collect();
+    Round {
+        jokers,
+        ..clone_round(round)
+    }
+}
+
+fn check_lineup(round: &Round) -> Result<(), String> {
+    if round.jokers.len() > MAX_JOKERS {
+        return Err(format!(
+            "{} jokers, at most {MAX_JOKERS} can be attributed",
+            round.jokers.len()
+        ));
+    }
+    Ok(())
+}
+
+pub fn total(round: &Round) -> f64 {
+    value(clone_round(round))
+}
+
+/// Score with every joker minus score with that one joker removed.
+pub fn joker_marginals(round: &Round) -> Result<Vec<f64>, String> {
+    check_lineup(round)?;
+    let all = (1u64 << round.jokers.len()) - 1;
+    let full = value(clone_round(round));
+    Ok((0..round.jokers.len())
+        .map(|i| full - value(with_jokers(round, all & !(1 << i))))
+        .collect())
+}
+
+/// Score minus score with that card removed, for played then held cards.
+pub fn card_marginals(round: &Round) -> (Vec<f64>, Vec<f64>) {
+    let full = value(clone_round(round));
+    let played = (0..round.cards_played.len())
+        .map(|i| {
+            let mut without = clone_round(round);
+            without.cards_played.remove(i);
+            full - value(without)
+        })
+        .collect();
+    let held = (0..round.cards_held_in_hand.len())
+        .map(|i| {
+            let mut without = clone_round(round);
+            without.cards_held_in_hand.remove(i);
+            full - value(without)
+        })
+        .collect();
+    (played, held)
+}
+
+/// Scores joker subsets on demand, each at most once.
+struct Lineups<'a> {
+    round: &'a Round,
+    scores: HashMap<u64, f64>,
+}
+
+impl Lineups<'_> {
+    fn value(&mut self, mask: u64) -> f64 {
+        let round = self.round;
+        *self
+            .scores
+            .entry(mask)
+            .or_insert_with(|| value(with_jokers(round, mask)))
+    }
+}
+
+/// Each joker's Shapley value: its marginal contribution averaged over every
+/// order the lineup could have been assembled in. The values sum to the
+/// score with all jokers minus the score with none.
+pub fn shapley(
+    round: &Round,
+    samples: usize,
+    rng: &mut SeededRng,
+) -> Result<(Vec<f64>, Method), String> {
+    check_lineup(round)?;
+    let n = round.jokers.len();
+    let mut lineups = Lineups {
+        round,
+        scores: HashMap::new(),
+    };
+    let mut phi = vec![0.0; n];
+
+    if n <= EXACT_SHAPLEY_JOKERS {
+        // weight[k] = k! (n - k - 1)! / n! for a coalition of size k.
+        let mut weight = vec![1.0 / n as f64; n];
+        for k in 1..n {
+            weight[k] = weight[k - 1] * k as f64 / (n - k) as f64;
+        }
+        for mask in 0u64..1 << n {
+            let size = mask.count_ones() as usize;
+            for (i, phi) in phi.iter_mut().enumerate() {
+                if mask & 1 << i == 0 {
+                    let gain = lineups.value(mask | 1 << i) - lineups.value(mask);
+                    *phi += weight[size] * gain;
+                }
+            }
+        }
+        return Ok((phi, Method::Exact));
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    for _ in 0..samples {
+        rng.shuffle(&mut order);
+        let mut mask = 0u64;
+        let mut before = lineups.value(mask);
+        for &i in &order {
+            mask |= 1 << i;
+            let after = lineups.value(mask);
+            phi[i] += after - before;
+            before = after;
+        }
+    }
+    for phi in &mut phi {
+        *phi /= samples as f64;
+    }
+    Ok((phi, Method::Sampled(samples)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ortalib::{Card, Joker, Rank, Suit};
+
+    fn round(jokers: &[Joker]) -> Round {
+        Round {
+            cards_played: vec![
+                Card::new(Rank::King, Suit::Spades, None, None),
+                Card::new(Rank::King, Suit::Hearts, None, None),
+            ],
+            cards_held_in_hand: Vec::new(),
+            jokers: jokers.iter().map(|&j| JokerCard::new(j, None)).collect(),
+        }
+    }
+
+    fn without_jokers(round: &Round) -> f64 {
+        total(&Round {
+            jokers: Vec::new(),
+            ..clone_round(round)
+        })
+    }
+
+    #[test]
+    fn exact_shapley_values_add_up_to_the_jokers_contribution() {
+        let round = round(&[Joker::Joker, Joker::JollyJoker, Joker::SlyJoker]);
+        let (values, method) = shapley(&round, 10, &mut SeededRng::new(0)).unwrap();
+        assert_eq!(method, Method::Exact);
+        let sum: f64 = values.iter().sum();
+        assert!((sum - (total(&round) - without_jokers(&round))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sampled_shapley_values_add_up_too() {
+        let round = round(&[Joker::Joker; EXACT_SHAPLEY_JOKERS + 1]);
+        let (values, method) = shapley(&round, 50, &mut SeededRng::new(0)).unwrap();
+        assert_eq!(method, Method::Sampled(50));
+        let sum: f64 = values.iter().sum();
+        assert!((sum - (total(&round) - without_jokers(&round))).abs() < 1e-6);
+    }
+
+    #[test]
+    fn marginals_remove_one_joker_at_a_time() {
+        let round = round(&[Joker::Joker, Joker::SlyJoker]);
+        let marginals = joker_marginals(&round).unwrap();
+        // Pair of Kings: 10 + 20 chips. Sly adds 50 chips, Joker 4 mult.
+        assert_eq!(
+            marginals,
+            vec![80.0 * 6.0 - 80.0 * 2.0, 80.0 * 6.0 - 30.0 * 6.0]
+        );
+    }
+
+    #[test]
+    fn lineups_too_wide_for_a_mask_are_rejected() {
+        let widest = round(&[Joker::Joker; MAX_JOKERS]);
+        assert!(joker_marginals(&widest).is_ok());
+        let too_wide = round(&[Joker::Joker; MAX_JOKERS + 1]);
+        assert!(joker_marginals(&too_wide).is_err());
+        assert!(shapley(&too_wide, 1, &mut SeededRng::new(0)).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use super::consumables::{Spectral, SpectralOutcome, Tarot, apply_spectral, apply_tarot};
+use super::deck::Deck;
+use super::levels::HandLevels;
+use super::rng::SeededRng;
+use super::sorce::Sorce;
+use crate::evaluate_seeded;
+use ortalib::{Card, JokerCard, Round};
+
+/// Most cards a single play or discard may use.
+pub const MAX_SELECTED: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlindConfig {
+    pub target: f64,
+    pub hands: usize,
+    pub discards: usize,
+    pub hand_size: usize,
+}
+
+impl Default for BlindConfig {
+    /// The game's Small Blind at ante 1 with a standard deck.
+    fn default() -> Self {
+        Self {
+            target: 300.0,
+            hands: 4,
+            discards: 3,
+            hand_size: 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlindStatus {
+    InProgress,
+    Won,
+    Lost,
+}
+
+/// One blind: draw a hand, then play or discard until the target is
+/// reached (won) or the hands run out (lost).
+pub struct Blind {
+    pub config: BlindConfig,
+    pub deck: Deck,
+    pub jokers: Vec<JokerCard>,
+    pub levels: HandLevels,
+    hand: Vec<Card>,
+    score: f64,
+    hands_left: usize,
+    discards_left: usize,
+    status: BlindStatus,
+    rng: SeededRng,
+}
+
+impl Blind {
+    /// Deals the opening hand. `rng` rolls chance-based scoring effects.
+    pub fn new(config: BlindConfig, deck: Deck, jokers: Vec<JokerCard>, rng: SeededRng) -> Self {
+        let mut blind = Self {
+            config,
+            deck,
+            jokers,
+            levels: HandLevels::default(),
+            hand: Vec::new(),
+            score: 0.0,
+            hands_left: config.hands,
+            discards_left: config.discards,
+            status: BlindStatus::InProgress,
+            rng,
+        };
+        blind.refill();
+        blind
+    }
+
+    pub fn hand(&self) -> &[Card] {
+        &self.hand
+    }
+
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    pub fn hands_left(&self) -> usize {
+        self.hands_left
+    }
+
+    pub fn discards_left(&self) -> usize {
+        self.discards_left
+    }
+
+    pub fn status(&self) -> BlindStatus {
+        self.status
+    }
+
+    pub fn discards_used(&self) -> usize {
+        self.config.discards - self.discards_left
+    }
+
+    /// Every card the player owns: the hand and both piles.
+    pub fn full_deck(&self) -> Vec<Card> {
+        let piles = self.deck.draw_pile().iter().chain(self.deck.discard_pile());
+        self.hand.iter().chain(piles).copied().collect()
+    }
+
+    fn refill(&mut self) {
+        let missing = self.config.hand_size.saturating_sub(self.hand.len());
+        self.hand.extend(self.deck.draw(missing));
+        if self.hand.is_empty() && self.status == BlindStatus::InProgress {
+            self.status = BlindStatus::Lost;
+        }
+    }
+
+    /// Splits the hand into the selected cards, in selection order, and the
+    /// rest. Indices are 0-based.
+    fn select(&self, indices: &[usize]) -> Result<(Vec<Card>, Vec<Card>), String> {
+        if self.status != BlindStatus::InProgress {
+            return Err("the blind is over".to_string());
+        }
+        if indices.is_empty() || indices.len() > MAX_SELECTED {
+            return Err(format!("select between 1 and {MAX_SELECTED} cards"));
+        }
+        for (n, &i) in indices.iter().enumerate() {
+            if i >= self.hand.len() {
+                return Err(format!(
+                    "no card #{} (the hand has {})",
+                    i + 1,
+                    self.hand.len()
+                ));
+            }
+            if indices[..n].contains(&i) {
+                return Err(format!("card #{} selected twice", i + 1));
+            }
+        }
+        let selected = indices.iter().map(|&i| self.hand[i]).collect();
+        let rest = (0..self.hand.len())
+            .filter(|i| !indices.contains(i))
+            .map(|i| self.hand[i])
+            .collect();
+        Ok((selected, rest))
+    }
+
+    /// Plays the selected cards with the rest held in hand. Played cards go
+    /// to the discard pile unless they were destroyed while scoring.
+    pub fn play(&mut self, indices: &[usize]) -> Result<Sorce, String> {
+        if self.hands_left == 0 {
+            return Err("no hands left".to_string());
+        }
+        let (played, held) = self.select(indices)?;
+        let round = Round {
+            cards_played: played.clone(),
+            cards_held_in_hand: held.clone(),
+            jokers: self.jokers.clone(),
+        };
+        let mut sorce = evaluate_seeded(round, Some(&mut self.rng));
+        self.levels.apply(&mut sorce);
+
+        self.score += (sorce.total_chips * sorce.mult).floor();
+        self.hands_left -= 1;
+        let kept: Vec<Card> = played
+            .into_iter()
+            .filter(|card| !sorce.destroyed.contains(card))
+            .collect();
+        self.deck.discard(&kept);
+        self.hand = held;
+
+        if self.score >= self.config.target {
+            self.status = BlindStatus::Won;
+        } else if self.hands_left == 0 {
+            self.status = BlindStatus::Lost;
+        } else {
+            self.refill();
+        }
+        Ok(sorce)
+    }
+
+    pub fn discard(&mut self, indices: &[usize]) -> Result<(), String> {
+        if self.discards_left == 0 {
+            return Err("no discards left".to_string());
+        }
+        let (discarded, rest) = self.select(indices)?;
+        self.deck.discard(&discarded);
+        self.hand = rest;
+        self.discards_left -= 1;
+        self.refill();
+        Ok(())
+    }
+
+    /// Uses a spectral on the hand. Destroyed cards leave the game and
+    /// created cards join the hand, reaching the deck once played or
+    /// discarded. The next refill draws up to the changed hand size.
+    pub fn use_spectral(
+        &mut self,
+        spectral: Spectral,
+        indices: &[usize],
+        rng: &mut SeededRng,
+    ) -> Result<SpectralOutcome, String> {
+        if self.status != BlindStatus::InProgress {
+            return Err("the blind is over".to_string());
+        }
+        let outcome = apply_spectral(spectral, &mut self.hand, indices, rng)?;
+        for card in &outcome.destroyed {
+            if let Some(i) = self.hand.iter().position(|c| c == card) {
+                self.hand.remove(i);
+            }
+        }
+        self.hand.extend(&outcome.created);
+        let hand_size = self.config.hand_size as i32 + outcome.hand_size_change;
+        self.config.hand_size = hand_size.max(1) as usize;
+        Ok(outcome)
+    }
+
+    /// Uses a tarot on the selected cards, changing them in hand.
+    pub fn use_tarot(&mut self, tarot: Tarot, indices: &[usize]) -> Result<Vec<Card>, String> {
+        let (selected, _) = self.select(indices)?;
+        let changed = apply_tarot(tarot, &selected)?;
+        for (&i, card) in indices.iter().zip(&changed) {
+            self.hand[i] = *card;
+        }
+        Ok(changed)
+    }
+
+    /// Ends the blind, returning the hand to the deck's discard pile.
+    pub fn finish(mut self) -> Deck {
+        self.deck.discard(&self.hand);
+        self.deck
+    }
+}
@@ -0,0 +1,236 @@
+use enum_iterator::all;
+use ortalib::{Joker, JokerCard};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+/// When a joker's effect is resolved during scoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    /// Once, in the joker phase after every card has scored.
+    Independent,
+    /// In the joker phase, only if the played hand contains a given hand type.
+    HandConditional,
+    /// Once per scoring played card.
+    OnScored,
+    /// Once per card held in hand.
+    OnHeld,
+    /// Changes the rules (hand detection, face cards, ...) rather than scoring.
+    Passive,
+    /// Whatever the joker it copies does.
+    Copy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JokerInfo {
+    pub joker: Joker,
+    pub description: &'static str,
+    pub rarity: Rarity,
+    pub trigger: Trigger,
+    /// The effect resolves at the joker's slot in the joker phase, between
+    /// its Foil or Holographic and its Polychrome. Other jokers' effects
+    /// land earlier, while cards score, and only their edition waits for
+    /// the slot.
+    pub edition_timing: bool,
+    /// Shop price in dollars before any edition.
+    pub cost: u32,
+    /// Whether `Sorce::get_card` applies the effect; tested against the
+    /// scorer so the two cannot drift apart.
+    pub implemented: bool,
+}
+
+/// The registry every other joker query goes through. The match has no
+/// wildcard so a new `ortalib::Joker` variant will not compile until it is
+/// described here.
+pub fn info(joker: Joker) -> JokerInfo {
+    use Rarity::*;
+    use Trigger::*;
+
+    #[rustfmt::skip]
+    let (description, rarity, trigger, cost, implemented) = match joker {
+        Joker::Joker =>           ("+4 Mult", Common, Independent, 2, true),
+        Joker::JollyJoker =>      ("+8 Mult if played hand contains a Pair", Common, HandConditional, 3, true),
+        Joker::ZanyJoker =>       ("+12 Mult if played hand contains a Three of a Kind", Common, HandConditional, 4, true),
+        Joker::MadJoker =>        ("+10 Mult if played hand contains a Two Pair", Common, HandConditional, 4, true),
+        Joker::CrazyJoker =>      ("+12 Mult if played hand contains a Straight", Common, HandConditional, 4, true),
+        Joker::DrollJoker =>      ("+10 Mult if played hand contains a Flush", Common, HandConditional, 4, true),
+        Joker::SlyJoker =>        ("+50 Chips if played hand contains a Pair", Common, HandConditional, 3, true),
+        Joker::WilyJoker =>       ("+100 Chips if played hand contains a Three of a Kind", Common, HandConditional, 4, true),
+        Joker::CleverJoker =>     ("+80 Chips if played hand contains a Two Pair", Common, HandConditional, 4, true),
+        Joker::DeviousJoker =>    ("+100 Chips if played hand contains a Straight", Common, HandConditional, 4, true),
+        Joker::CraftyJoker =>     ("+80 Chips if played hand contains a Flush", Common, HandConditional, 4, true),
+        Joker::AbstractJoker =>   ("+3 Mult for each Joker card", Common, Independent, 4, true),
+        Joker::RaisedFist =>      ("Adds double the rank of lowest ranked card held in hand to Mult", Common, OnHeld, 5, true),
+        Joker::Blackboard =>      ("x3 Mult if all cards held in hand are Spades or Clubs", Uncommon, Independent, 6, true),
+        Joker::Baron =>           ("Each King held in hand gives x1.5 Mult", Rare, OnHeld, 8, true),
+        Joker::GreedyJoker =>     ("Played cards with Diamond suit give +3 Mult when scored", Common, OnScored, 5, true),
+        Joker::LustyJoker =>      ("Played cards with Heart suit give +3 Mult when scored", Common, OnScored, 5, true),
+        Joker::WrathfulJoker =>   ("Played cards with Spade suit give +3 Mult when scored", Common, OnScored, 5, true),
+        Joker::GluttonousJoker => ("Played cards with Club suit give +3 Mult when scored", Common, OnScored, 5, true),
+        Joker::Fibonacci =>       ("Each played Ace, 2, 3, 5, or 8 gives +8 Mult when scored", Uncommon, OnScored, 8, true),
+        Joker::ScaryFace =>       ("Played face cards give +30 Chips when scored", Common, OnScored, 4, true),
+        Joker::EvenSteven =>      ("Played cards with even rank give +4 Mult when scored", Common, OnScored, 4, true),
+        Joker::OddTodd =>         ("Played cards with odd rank give +31 Chips when scored", Common, OnScored, 4, true),
+        Joker::Photograph =>      ("First played face card gives x2 Mult when scored", Common, OnScored, 5, true),
+        Joker::SmileyFace =>      ("Played face cards give +5 Mult when scored", Common, OnScored, 4, true),
+        Joker::FlowerPot =>       ("x3 Mult if poker hand contains a Diamond, Club, Heart and Spade card", Uncommon, Independent, 6, true),
+        Joker::FourFingers =>     ("All Flushes and Straights can be made with 4 cards", Uncommon, Passive, 7, false),
+        Joker::Shortcut =>        ("Allows Straights to be made with gaps of 1 rank", Uncommon, Passive, 7, false),
+        Joker::Mime =>            ("Retrigger all card held in hand abilities", Uncommon, OnHeld, 5, true),
+        Joker::Pareidolia =>      ("All cards are considered face cards", Uncommon, Passive, 5, false),
+        Joker::Splash =>          ("Every played card counts in scoring", Common, Passive, 3, false),
+        Joker::SockAndBuskin =>   ("Retrigger all played face cards", Uncommon, OnScored, 6, false),
+        Joker::SmearedJoker =>    ("Hearts and Diamonds count as the same suit, Spades and Clubs count as the same suit", Uncommon, Passive, 7, false),
+        Joker::Blueprint =>       ("Copies the ability of Joker to the right", Rare, Copy, 10, false),
+    };
+
+    JokerInfo {
+        joker,
+        description,
+        rarity,
+        trigger,
+        edition_timing: matches!(trigger, Independent | HandConditional),
+        cost,
+        implemented,
+    }
+}
+
+pub fn is_implemented(joker: Joker) -> bool {
+    info(joker).implemented
+}
+
+/// The distinct jokers in a lineup whose effects the engine would ignore.
+pub fn unimplemented(jokers: &[JokerCard]) -> Vec<Joker> {
+    let mut missing: Vec<Joker> = jokers
+        .iter()
+        .map(|j| j.joker)
+        .filter(|j| !is_implemented(*j))
+        .collect();
+    missing.sort();
+    missing.dedup();
+    missing
+}
+
+/// Every joker in `ortalib` declaration order.
+pub fn catalog() -> Vec<JokerInfo> {
+    all::<Joker>().map(info).collect()
+}
+
+impl Display for Rarity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Rarity::Common => "common",
+            Rarity::Uncommon => "uncommon",
+            Rarity::Rare => "rare",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Rarity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "common" => Ok(Rarity::Common),
+            "uncommon" => Ok(Rarity::Uncommon),
+            "rare" => Ok(Rarity::Rare),
+            _ => Err(format!("Invalid Rarity: `{s}`")),
+        }
+    }
+}
+
+impl Display for Trigger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Trigger::Independent => "independent",
+            Trigger::HandConditional => "hand-conditional",
+            Trigger::OnScored => "on-scored",
+            Trigger::OnHeld => "on-held",
+            Trigger::Passive => "passive",
+            Trigger::Copy => "copy",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Trigger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "independent" => Ok(Trigger::Independent),
+            "hand-conditional" => Ok(Trigger::HandConditional),
+            "on-scored" => Ok(Trigger::OnScored),
+            "on-held" => Ok(Trigger::OnHeld),
+            "passive" => Ok(Trigger::Passive),
+            "copy" => Ok(Trigger::Copy),
+            _ => Err(format!("Invalid Trigger: `{s}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::generator::{GenConfig, RoundGenerator};
+    use crate::models::round::clone_round;
+    use ortalib::{Enhancement, PokerHand, Round};
+
+    /// Rounds of every hand type, with enhanced cards held so held-card
+    /// jokers have something to act on.
+    fn rounds() -> Vec<Round> {
+        let mut rounds = Vec::new();
+        for hand in all::<PokerHand>() {
+            let config = GenConfig {
+                hand: Some(hand),
+                enhancement_chance: all::<Enhancement>().map(|e| (e, 0.1)).collect(),
+                held_min: 1,
+                held_max: 5,
+                ..GenConfig::default()
+            };
+            let mut generator = RoundGenerator::new(config, 1);
+            for _ in 0..40 {
+                rounds.push(generator.next_round().unwrap());
+            }
+        }
+        rounds
+    }
+
+    fn score_with(round: &Round, jokers: &[Joker]) -> f64 {
+        let mut round = clone_round(round);
+        round.jokers = jokers.iter().map(|&j| JokerCard::new(j, None)).collect();
+        let (chips, mult) = crate::score(round);
+        chips * mult
+    }
+
+    /// Whether adding `joker` on either side of a plain Joker, or alone,
+    /// changes the score of any of `rounds`.
+    fn changes_score(rounds: &[Round], joker: Joker) -> bool {
+        rounds.iter().any(|round| {
+            let alone = score_with(round, &[]);
+            let beside = score_with(round, &[Joker::Joker]);
+            score_with(round, &[joker]) != alone
+                || score_with(round, &[joker, Joker::Joker]) != beside
+                || score_with(round, &[Joker::Joker, joker]) != beside
+        })
+    }
+
+    #[test]
+    fn implemented_flags_match_the_scorer() {
+        let rounds = rounds();
+        let wrong: Vec<Joker> = all::<Joker>()
+            .filter(|&joker| changes_score(&rounds, joker) != is_implemented(joker))
+            .collect();
+        assert!(
+            wrong.is_empty(),
+            "catalog disagrees with the scorer on {wrong:?}"
+        );
+    }
+}
@@ -0,0 +1,468 @@
+use super::rng::SeededRng;
+use ortalib::{Card, Edition, Enhancement, PokerHand, Rank, Suit};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The tarot cards that change playing cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tarot {
+    TheMagician,
+    TheEmpress,
+    TheHierophant,
+    TheLovers,
+    TheChariot,
+    Justice,
+    Strength,
+    Death,
+    TheStar,
+    TheMoon,
+    TheSun,
+    TheWorld,
+}
+
+impl Tarot {
+    pub const ALL: [Tarot; 12] = [
+        Tarot::TheMagician,
+        Tarot::TheEmpress,
+        Tarot::TheHierophant,
+        Tarot::TheLovers,
+        Tarot::TheChariot,
+        Tarot::Justice,
+        Tarot::Strength,
+        Tarot::Death,
+        Tarot::TheStar,
+        Tarot::TheMoon,
+        Tarot::TheSun,
+        Tarot::TheWorld,
+    ];
+
+    /// How many cards the tarot needs selected, as `(min, max)`.
+    pub fn targets(self) -> (usize, usize) {
+        match self {
+            Tarot::TheMagician | Tarot::TheEmpress | Tarot::TheHierophant | Tarot::Strength => {
+                (1, 2)
+            }
+            Tarot::TheLovers | Tarot::TheChariot | Tarot::Justice => (1, 1),
+            Tarot::Death => (2, 2),
+            Tarot::TheStar | Tarot::TheMoon | Tarot::TheSun | Tarot::TheWorld => (1, 3),
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Tarot::TheMagician => "Enhances up to 2 cards into Lucky Cards",
+            Tarot::TheEmpress => "Enhances up to 2 cards into Mult Cards",
+            Tarot::TheHierophant => "Enhances up to 2 cards into Bonus Cards",
+            Tarot::TheLovers => "Enhances 1 card into a Wild Card",
+            Tarot::TheChariot => "Enhances 1 card into a Steel Card",
+            Tarot::Justice => "Enhances 1 card into a Glass Card",
+            Tarot::Strength => "Increases rank of up to 2 cards by 1",
+            Tarot::Death => "Converts the first card into a copy of the second",
+            Tarot::TheStar => "Converts up to 3 cards to Diamonds",
+            Tarot::TheMoon => "Converts up to 3 cards to Clubs",
+            Tarot::TheSun => "Converts up to 3 cards to Hearts",
+            Tarot::TheWorld => "Converts up to 3 cards to Spades",
+        }
+    }
+}
+
+/// The rank Strength turns a card into; Aces wrap around to 2.
+fn next_rank(rank: Rank) -> Rank {
+    match rank {
+        Rank::Two => Rank::Three,
+        Rank::Three => Rank::Four,
+        Rank::Four => Rank::Five,
+        Rank::Five => Rank::Six,
+        Rank::Six => Rank::Seven,
+        Rank::Seven => Rank::Eight,
+        Rank::Eight => Rank::Nine,
+        Rank::Nine => Rank::Ten,
+        Rank::Ten => Rank::Jack,
+        Rank::Jack => Rank::Queen,
+        Rank::Queen => Rank::King,
+        Rank::King => Rank::Ace,
+        Rank::Ace => Rank::Two,
+    }
+}
+
+/// Uses a tarot on `targets`, in selection order, and returns the cards as
+/// they are afterwards. Cards are edited in place, so each keeps its
+/// identity in the round or deck it came from.
+pub fn apply_tarot(tarot: Tarot, targets: &[Card]) -> Result<Vec<Card>, String> {
+    let (min, max) = tarot.targets();
+    if !(min..=max).contains(&targets.len()) {
+        let expected = if min == max {
+            min.to_string()
+        } else {
+            format!("{min} to {max}")
+        };
+        return Err(format!(
+            "{tarot} needs {expected} cards, got {}",
+            targets.len()
+        ));
+    }
+
+    let edit = |change: &dyn Fn(&mut Card)| {
+        targets
+            .iter()
+            .map(|card| {
+                let mut card = *card;
+                change(&mut card);
+                card
+            })
+            .collect()
+    };
+    let enhance = |enhancement| edit(&|card| card.enhancement = Some(enhancement));
+    let convert = |suit| edit(&|card| card.suit = suit);
+
+    Ok(match tarot {
+        Tarot::TheMagician => {
+            return Err("Lucky Cards are not modelled by ortalib::Enhancement".to_string());
+        }
+        Tarot::TheEmpress => enhance(Enhancement::Mult),
+        Tarot::TheHierophant => enhance(Enhancement::Bonus),
+        Tarot::TheLovers => enhance(Enhancement::Wild),
+        Tarot::TheChariot => enhance(Enhancement::Steel),
+        Tarot::Justice => enhance(Enhancement::Glass),
+        Tarot::Strength => edit(&|card| card.rank = next_rank(card.rank)),
+        Tarot::Death => {
+            let (mut converted, original) = (targets[0], targets[1]);
+            converted.rank = original.rank;
+            converted.suit = original.suit;
+            converted.enhancement = original.enhancement;
+            converted.edition = original.edition;
+            vec![converted, original]
+        }
+        Tarot::TheStar => convert(Suit::Diamonds),
+        Tarot::TheMoon => convert(Suit::Clubs),
+        Tarot::TheSun => convert(Suit::Hearts),
+        Tarot::TheWorld => convert(Suit::Spades),
+    })
+}
+
+impl Display for Tarot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Tarot::TheMagician => "The Magician",
+            Tarot::TheEmpress => "The Empress",
+            Tarot::TheHierophant => "The Hierophant",
+            Tarot::TheLovers => "The Lovers",
+            Tarot::TheChariot => "The Chariot",
+            Tarot::Justice => "Justice",
+            Tarot::Strength => "Strength",
+            Tarot::Death => "Death",
+            Tarot::TheStar => "The Star",
+            Tarot::TheMoon => "The Moon",
+            Tarot::TheSun => "The Sun",
+            Tarot::TheWorld => "The World",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Tarot {
+    type Err = String;
+
+    /// Accepts the card name with or without "The", in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wanted = s.trim().to_ascii_lowercase();
+        let wanted = wanted.strip_prefix("the ").unwrap_or(&wanted);
+        Tarot::ALL
+            .into_iter()
+            .find(|tarot| {
+                let name = tarot.to_string().to_ascii_lowercase();
+                name.strip_prefix("the ").unwrap_or(&name) == wanted
+            })
+            .ok_or_else(|| format!("Invalid Tarot: `{s}`"))
+    }
+}
+
+/// The planet cards, each levelling up one poker hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Planet {
+    Pluto,
+    Mercury,
+    Uranus,
+    Venus,
+    Saturn,
+    Jupiter,
+    Earth,
+    Mars,
+    Neptune,
+    PlanetX,
+    Ceres,
+    Eris,
+}
+
+impl Planet {
+    pub const ALL: [Planet; 12] = [
+        Planet::Pluto,
+        Planet::Mercury,
+        Planet::Uranus,
+        Planet::Venus,
+        Planet::Saturn,
+        Planet::Jupiter,
+        Planet::Earth,
+        Planet::Mars,
+        Planet::Neptune,
+        Planet::PlanetX,
+        Planet::Ceres,
+        Planet::Eris,
+    ];
+
+    pub fn hand(self) -> PokerHand {
+        match self {
+            Planet::Pluto => PokerHand::HighCard,
+            Planet::Mercury => PokerHand::Pair,
+            Planet::Uranus => PokerHand::TwoPair,
+            Planet::Venus => PokerHand::ThreeOfAKind,
+            Planet::Saturn => PokerHand::Straight,
+            Planet::Jupiter => PokerHand::Flush,
+            Planet::Earth => PokerHand::FullHouse,
+            Planet::Mars => PokerHand::FourOfAKind,
+            Planet::Neptune => PokerHand::StraightFlush,
+            Planet::PlanetX => PokerHand::FiveOfAKind,
+            Planet::Ceres => PokerHand::FlushHouse,
+            Planet::Eris => PokerHand::FlushFive,
+        }
+    }
+
+    /// Whether the planet only shows up once its hand has been played.
+    pub fn is_secret(self) -> bool {
+        matches!(self, Planet::PlanetX | Planet::Ceres | Planet::Eris)
+    }
+}
+
+/// The spectral cards that change playing cards in hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spectral {
+    Familiar,
+    Grim,
+    Incantation,
+    Aura,
+    Sigil,
+    Ouija,
+    Cryptid,
+}
+
+impl Spectral {
+    pub const ALL: [Spectral; 7] = [
+        Spectral::Familiar,
+        Spectral::Grim,
+        Spectral::Incantation,
+        Spectral::Aura,
+        Spectral::Sigil,
+        Spectral::Ouija,
+        Spectral::Cryptid,
+    ];
+
+    /// How many cards the spectral needs selected.
+    pub fn targets(self) -> usize {
+        match self {
+            Spectral::Aura | Spectral::Cryptid => 1,
+            _ => 0,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Spectral::Familiar => {
+                "Destroy 1 random card in hand, add 3 random Enhanced face cards to hand"
+            }
+            Spectral::Grim => "Destroy 1 random card in hand, add 2 random Enhanced Aces to hand",
+            Spectral::Incantation => {
+                "Destroy 1 random card in hand, add 4 random Enhanced numbered cards to hand"
+            }
+            Spectral::Aura => "Add Foil, Holographic, or Polychrome effect to 1 selected card",
+            Spectral::Sigil => "Converts all cards in hand to a single random suit",
+            Spectral::Ouija => "Converts all cards in hand to a single random rank, -1 hand size",
+            Spectral::Cryptid => "Create 2 copies of 1 selected card in hand",
+        }
+    }
+}
+
+/// What a spectral did besides editing cards in place.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpectralOutcome {
+    /// Cards to take out of the hand and the deck for good.
+    pub destroyed: Vec<Card>,
+    /// New cards to add to the hand; they join the deck from then on.
+    pub created: Vec<Card>,
+    pub hand_size_change: i32,
+}
+
+const ENHANCEMENTS: [Enhancement; 5] = [
+    Enhancement::Bonus,
+    Enhancement::Mult,
+    Enhancement::Wild,
+    Enhancement::Glass,
+    Enhancement::Steel,
+];
+const EDITIONS: [Edition; 3] = [Edition::Foil, Edition::Holographic, Edition::Polychrome];
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+const FACES: [Rank; 3] = [Rank::Jack, Rank::Queen, Rank::King];
+const NUMBERS: [Rank; 9] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+];
+const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+/// Destroys one random card in hand and creates `count` enhanced cards with
+/// ranks from `ranks`.
+fn replace_random(
+    hand: &[Card],
+    ranks: &[Rank],
+    count: usize,
+    rng: &mut SeededRng,
+) -> Result<SpectralOutcome, String> {
+    if hand.is_empty() {
+        return Err("there is no card in hand to destroy".to_string());
+    }
+    let destroyed = vec![rng.choose(hand)];
+    let created = (0..count)
+        .map(|_| {
+            let enhancement = rng.choose(&ENHANCEMENTS);
+            Card::new(
+                rng.choose(ranks),
+                rng.choose(&SUITS),
+                Some(enhancement),
+                None,
+            )
+        })
+        .collect();
+    Ok(SpectralOutcome {
+        destroyed,
+        created,
+        hand_size_change: 0,
+    })
+}
+
+/// Uses a spectral on the cards in hand. Cards are changed in place;
+/// destroyed and created cards are reported for the caller to remove and
+/// add, since they also leave or join the deck. `selected` indexes `hand`.
+pub fn apply_spectral(
+    spectral: Spectral,
+    hand: &mut [Card],
+    selected: &[usize],
+    rng: &mut SeededRng,
+) -> Result<SpectralOutcome, String> {
+    if selected.len() != spectral.targets() {
+        return Err(format!(
+            "{spectral} needs {} selected cards, got {}",
+            spectral.targets(),
+            selected.len()
+        ));
+    }
+    if let Some(&i) = selected.iter().find(|&&i| i >= hand.len()) {
+        return Err(format!("there is no card #{} in hand", i + 1));
+    }
+
+    match spectral {
+        Spectral::Familiar => replace_random(hand, &FACES, 3, rng),
+        Spectral::Grim => replace_random(hand, &[Rank::Ace], 2, rng),
+        Spectral::Incantation => replace_random(hand, &NUMBERS, 4, rng),
+        Spectral::Aura => {
+            hand[selected[0]].edition = Some(rng.choose(&EDITIONS));
+            Ok(SpectralOutcome::default())
+        }
+        Spectral::Sigil => {
+            let suit = rng.choose(&SUITS);
+            hand.iter_mut().for_each(|card| card.suit = suit);
+            Ok(SpectralOutcome::default())
+        }
+        Spectral::Ouija => {
+            let rank = rng.choose(&RANKS);
+            hand.iter_mut().for_each(|card| card.rank = rank);
+            Ok(SpectralOutcome {
+                hand_size_change: -1,
+                ..SpectralOutcome::default()
+            })
+        }
+        Spectral::Cryptid => {
+            let card = hand[selected[0]];
+            let copy = || Card::new(card.rank, card.suit, card.enhancement, card.edition);
+            Ok(SpectralOutcome {
+                created: vec![copy(), copy()],
+                ..SpectralOutcome::default()
+            })
+        }
+    }
+}
+
+impl Display for Spectral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Spectral::Familiar => "Familiar",
+            Spectral::Grim => "Grim",
+            Spectral::Incantation => "Incantation",
+            Spectral::Aura => "Aura",
+            Spectral::Sigil => "Sigil",
+            Spectral::Ouija => "Ouija",
+            Spectral::Cryptid => "Cryptid",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Spectral {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Spectral::ALL
+            .into_iter()
+            .find(|spectral| spectral.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("Invalid Spectral: `{s}`"))
+    }
+}
+
+impl Display for Planet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Planet::Pluto => "Pluto",
+            Planet::Mercury => "Mercury",
+            Planet::Uranus => "Uranus",
+            Planet::Venus => "Venus",
+            Planet::Saturn => "Saturn",
+            Planet::Jupiter => "Jupiter",
+            Planet::Earth => "Earth",
+            Planet::Mars => "Mars",
+            Planet::Neptune => "Neptune",
+            Planet::PlanetX => "Planet X",
+            Planet::Ceres => "Ceres",
+            Planet::Eris => "Eris",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Planet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Planet::ALL
+            .into_iter()
+            .find(|planet| planet.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("Invalid Planet: `{s}`"))
+    }
+}
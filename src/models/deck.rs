@@ -0,0 +1,180 @@
+use super::rng::SeededRng;
+use enum_iterator::all;
+use ortalib::{Card, Rank, Suit};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The starting decks a run can be played with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeckKind {
+    Standard,
+    /// No Jacks, Queens or Kings: 40 cards.
+    Abandoned,
+    /// 26 Spades and 26 Hearts.
+    Checkered,
+    /// Every card's rank and suit is random.
+    Erratic,
+    /// The standard 52 cards, +2 hand size and -1 joker slot.
+    Painted,
+}
+
+impl DeckKind {
+    pub fn hand_size_modifier(self) -> i32 {
+        match self {
+            DeckKind::Painted => 2,
+            _ => 0,
+        }
+    }
+
+    pub fn joker_slot_modifier(self) -> i32 {
+        match self {
+            DeckKind::Painted => -1,
+            _ => 0,
+        }
+    }
+
+    /// The unshuffled cards this deck starts with. Only Erratic uses `rng`.
+    pub fn starting_cards(self, rng: &mut SeededRng) -> Vec<Card> {
+        let standard = || {
+            all::<Suit>()
+                .flat_map(|suit| all::<Rank>().map(move |rank| Card::new(rank, suit, None, None)))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            DeckKind::Standard | DeckKind::Painted => standard(),
+            DeckKind::Abandoned => standard()
+                .into_iter()
+                .filter(|card| !card.rank.is_face())
+                .collect(),
+            DeckKind::Checkered => standard()
+                .into_iter()
+                .map(|card| {
+                    let suit = match card.suit {
+                        Suit::Clubs => Suit::Spades,
+                        Suit::Diamonds => Suit::Hearts,
+                        suit => suit,
+                    };
+                    Card::new(card.rank, suit, None, None)
+                })
+                .collect(),
+            DeckKind::Erratic => {
+                let ranks: Vec<Rank> = all::<Rank>().collect();
+                let suits: Vec<Suit> = all::<Suit>().collect();
+                (0..52)
+                    .map(|_| Card::new(rng.choose(&ranks), rng.choose(&suits), None, None))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A deck mid-round: the cards still to draw and the ones already used.
+/// The top of the draw pile is the end of the vector.
+#[derive(Debug, Clone)]
+pub struct Deck {
+    pub kind: DeckKind,
+    draw_pile: Vec<Card>,
+    discard_pile: Vec<Card>,
+    rng: SeededRng,
+}
+
+impl Deck {
+    /// Builds and shuffles a fresh deck; the same seed always gives the
+    /// same cards in the same order.
+    pub fn new(kind: DeckKind, seed: u64) -> Self {
+        let mut rng = SeededRng::new(seed);
+        let draw_pile = kind.starting_cards(&mut rng);
+        let mut deck = Self {
+            kind,
+            draw_pile,
+            discard_pile: Vec::new(),
+            rng,
+        };
+        deck.shuffle();
+        deck
+    }
+
+    pub fn shuffle(&mut self) {
+        self.rng.shuffle(&mut self.draw_pile);
+    }
+
+    /// Returns the discard pile to the draw pile and shuffles everything.
+    pub fn reshuffle(&mut self) {
+        self.draw_pile.append(&mut self.discard_pile);
+        self.shuffle();
+    }
+
+    /// Draws up to `n` cards; fewer if the draw pile runs out.
+    pub fn draw(&mut self, n: usize) -> Vec<Card> {
+        let at = self.draw_pile.len().saturating_sub(n);
+        let mut drawn = self.draw_pile.split_off(at);
+        drawn.reverse();
+        drawn
+    }
+
+    pub fn discard(&mut self, cards: &[Card]) {
+        self.discard_pile.extend_from_slice(cards);
+    }
+
+    /// Puts a new card on top of the draw pile.
+    pub fn add(&mut self, card: Card) {
+        self.draw_pile.push(card);
+    }
+
+    /// Takes this exact card (not just one of the same rank and suit) out of
+    /// the deck for good. Returns whether it was found.
+    pub fn remove(&mut self, card: &Card) -> bool {
+        for pile in [&mut self.draw_pile, &mut self.discard_pile] {
+            if let Some(i) = pile.iter().position(|c| c == card) {
+                pile.remove(i);
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn draw_pile(&self) -> &[Card] {
+        &self.draw_pile
+    }
+
+    pub fn discard_pile(&self) -> &[Card] {
+        &self.discard_pile
+    }
+
+    /// Cards left to draw plus cards discarded.
+    pub fn len(&self) -> usize {
+        self.draw_pile.len() + self.discard_pile.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Display for DeckKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DeckKind::Standard => "standard",
+            DeckKind::Abandoned => "abandoned",
+            DeckKind::Checkered => "checkered",
+            DeckKind::Erratic => "erratic",
+            DeckKind::Painted => "painted",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for DeckKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "standard" => Ok(DeckKind::Standard),
+            "abandoned" => Ok(DeckKind::Abandoned),
+            "checkered" => Ok(DeckKind::Checkered),
+            "erratic" => Ok(DeckKind::Erratic),
+            "painted" => Ok(DeckKind::Painted),
+            _ => Err(format!("Invalid DeckKind: `{s}`")),
+        }
+    }
+}
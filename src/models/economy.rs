@@ -0,0 +1,149 @@
+use super::ante::{BlindKind, Stake};
+use ortalib::{Card, Rank};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Money a run starts with.
+pub const STARTING_MONEY: i64 = 4;
+/// Paid for each hand left when a blind is won.
+pub const PER_UNUSED_HAND: i64 = 1;
+/// Interest is $1 for every this many dollars held...
+pub const INTEREST_STEP: i64 = 5;
+/// ...up to this many dollars per round.
+pub const INTEREST_CAP: i64 = 5;
+
+/// The reward for beating a blind.
+pub fn blind_reward(blind: BlindKind, stake: Stake) -> i64 {
+    match blind {
+        BlindKind::Small if !stake.small_blind_reward() => 0,
+        BlindKind::Small => 3,
+        BlindKind::Big => 4,
+        BlindKind::Boss => 5,
+    }
+}
+
+/// Jokers that pay out at the end of a round. ortalib's `Joker` only covers
+/// scoring jokers, so these are listed separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoneyJoker {
+    /// $4 at end of round.
+    GoldenJoker,
+    /// $1 for each 9 in the full deck.
+    Cloud9,
+    /// $2 per discard left if no discards were used.
+    DelayedGratification,
+    /// $1 more interest for every $5 held, within the cap.
+    ToTheMoon,
+}
+
+impl MoneyJoker {
+    pub const ALL: [MoneyJoker; 4] = [
+        MoneyJoker::GoldenJoker,
+        MoneyJoker::Cloud9,
+        MoneyJoker::DelayedGratification,
+        MoneyJoker::ToTheMoon,
+    ];
+
+    /// What the joker pays itself at the end of `end`, if anything.
+    fn payout(self, end: &RoundEnd) -> i64 {
+        match self {
+            MoneyJoker::GoldenJoker => 4,
+            MoneyJoker::Cloud9 => end
+                .full_deck
+                .iter()
+                .filter(|c| c.rank == Rank::Nine)
+                .count() as i64,
+            MoneyJoker::DelayedGratification if end.discards_used == 0 => {
+                2 * end.discards_left as i64
+            }
+            MoneyJoker::DelayedGratification | MoneyJoker::ToTheMoon => 0,
+        }
+    }
+}
+
+/// The state of a won blind that the payout depends on.
+#[derive(Debug, Clone, Copy)]
+pub struct RoundEnd<'a> {
+    pub blind: BlindKind,
+    pub stake: Stake,
+    /// Money held before the payout.
+    pub money: i64,
+    pub hands_left: usize,
+    pub discards_left: usize,
+    pub discards_used: usize,
+    /// Every card the player owns: draw pile, discard pile and hand.
+    pub full_deck: &'a [Card],
+}
+
+/// One line of a payout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Earning {
+    pub source: String,
+    pub amount: i64,
+}
+
+/// Interest on `money`: $1 per `INTEREST_STEP` up to `INTEREST_CAP`, plus
+/// $1 per step for each To the Moon.
+pub fn interest(money: i64, jokers: &[MoneyJoker]) -> i64 {
+    let steps = (money.max(0) / INTEREST_STEP).min(INTEREST_CAP);
+    let per_step = 1 + jokers
+        .iter()
+        .filter(|&&j| j == MoneyJoker::ToTheMoon)
+        .count() as i64;
+    steps * per_step
+}
+
+/// The cash-out for a won blind, in the order the game pays it: blind
+/// reward, unused hands, interest, then jokers. Sources paying nothing are
+/// left out.
+pub fn payout(end: &RoundEnd, jokers: &[MoneyJoker]) -> Vec<Earning> {
+    let mut earnings = vec![
+        Earning {
+            source: end.blind.to_string(),
+            amount: blind_reward(end.blind, end.stake),
+        },
+        Earning {
+            source: format!("{} unused hands", end.hands_left),
+            amount: end.hands_left as i64 * PER_UNUSED_HAND,
+        },
+        Earning {
+            source: "Interest".to_string(),
+            amount: interest(end.money, jokers),
+        },
+    ];
+    earnings.extend(jokers.iter().map(|joker| Earning {
+        source: joker.to_string(),
+        amount: joker.payout(end),
+    }));
+    earnings.retain(|e| e.amount != 0);
+    earnings
+}
+
+pub fn total(earnings: &[Earning]) -> i64 {
+    earnings.iter().map(|e| e.amount).sum()
+}
+
+impl Display for MoneyJoker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            MoneyJoker::GoldenJoker => "Golden Joker",
+            MoneyJoker::Cloud9 => "Cloud 9",
+            MoneyJoker::DelayedGratification => "Delayed Gratification",
+            MoneyJoker::ToTheMoon => "To the Moon",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for MoneyJoker {
+    type Err = String;
+
+    /// Accepts the name in any case, with spaces, hyphens or neither.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let squash = |s: &str| s.replace([' ', '-'], "").to_ascii_lowercase();
+        MoneyJoker::ALL
+            .into_iter()
+            .find(|joker| squash(&joker.to_string()) == squash(s))
+            .ok_or_else(|| format!("Invalid MoneyJoker: `{s}`"))
+    }
+}
@@ -0,0 +1,191 @@
+use super::pokerhand::HandValue;
+use super::rng::SeededRng;
+use enum_iterator::all;
+use ortalib::{Card, Edition, Enhancement, Joker, JokerCard, PokerHand, Rank, Round, Suit};
+
+const MAX_ATTEMPTS: usize = 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct GenConfig {
+    pub hand: Option<PokerHand>,
+    pub jokers: Vec<JokerCard>,
+    pub random_jokers: usize,
+    pub enhancement_chance: Vec<(Enhancement, f64)>,
+    pub edition_chance: Vec<(Edition, f64)>,
+    pub held_min: usize,
+    pub held_max: usize,
+}
+
+pub struct RoundGenerator {
+    config: GenConfig,
+    rng: SeededRng,
+    ranks: Vec<Rank>,
+    suits: Vec<Suit>,
+}
+
+impl RoundGenerator {
+    pub fn new(config: GenConfig, seed: u64) -> Self {
+        Self {
+            config,
+            rng: SeededRng::new(seed),
+            ranks: all::<Rank>().collect(),
+            suits: all::<Suit>().collect(),
+        }
+    }
+
+    /// Builds the next round. Played cards are re-rolled until `HandValue`
+    /// agrees with the forced hand, so every round is one the scorer accepts.
+    pub fn next_round(&mut self) -> Result<Round, String> {
+        let cards_played = match self.config.hand {
+            Some(target) => {
+                let mut attempts = 0;
+                loop {
+                    let plain = self.cards_for(target);
+                    let cards = self.decorate(plain);
+                    if HandValue::evaluation(&cards, &[], &[]).hand == target {
+                        break cards;
+                    }
+                    attempts += 1;
+                    if attempts >= MAX_ATTEMPTS {
+                        return Err(format!(
+                            "could not generate a {target} after {MAX_ATTEMPTS} attempts"
+                        ));
+                    }
+                }
+            }
+            None => {
+                let len = self.rng.range(1, 5);
+                let cards = (0..len).map(|_| self.random_card()).collect();
+                self.decorate(cards)
+            }
+        };
+
+        let held_len = self.rng.range(self.config.held_min, self.config.held_max);
+        let held = (0..held_len).map(|_| self.random_card()).collect();
+        let cards_held_in_hand = self.decorate(held);
+
+        let mut jokers = self.config.jokers.clone();
+        let extra = self.rng.range(0, self.config.random_jokers);
+        let joker_pool: Vec<Joker> = all::<Joker>().collect();
+        for _ in 0..extra {
+            let joker = self.rng.choose(&joker_pool);
+            let edition = self.roll_edition();
+            jokers.push(JokerCard::new(joker, edition));
+        }
+
+        Ok(Round {
+            cards_played,
+            cards_held_in_hand,
+            jokers,
+        })
+    }
+
+    fn random_card(&mut self) -> Card {
+        let rank = self.rng.choose(&self.ranks);
+        let suit = self.rng.choose(&self.suits);
+        Card::new(rank, suit, None, None)
+    }
+
+    fn decorate(&mut self, cards: Vec<Card>) -> Vec<Card> {
+        cards
+            .into_iter()
+            .map(|c| {
+                let enhancement = Self::roll(&mut self.rng, &self.config.enhancement_chance);
+                let edition = self.roll_edition();
+                Card::new(c.rank, c.suit, enhancement, edition)
+            })
+            .collect()
+    }
+
+    fn roll_edition(&mut self) -> Option<Edition> {
+        Self::roll(&mut self.rng, &self.config.edition_chance)
+    }
+
+    fn roll<T: Copy>(rng: &mut SeededRng, chances: &[(T, f64)]) -> Option<T> {
+        let roll = rng.next_f64();
+        let mut acc = 0.0;
+        for &(value, chance) in chances {
+            acc += chance;
+            if roll < acc {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// `count` distinct ranks, taken from a shuffled copy of every rank.
+    fn distinct_ranks(&mut self, count: usize) -> Vec<Rank> {
+        let mut ranks = self.ranks.clone();
+        self.rng.shuffle(&mut ranks);
+        ranks.truncate(count);
+        ranks
+    }
+
+    fn straight_ranks(&mut self) -> Vec<Rank> {
+        // Index 0 is the wheel (A 2 3 4 5), 1..=9 start at Two..Ten.
+        let start = self.rng.below(10);
+        if start == 0 {
+            vec![Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five]
+        } else {
+            self.ranks[start - 1..start + 4].to_vec()
+        }
+    }
+
+    fn cards_for(&mut self, hand: PokerHand) -> Vec<Card> {
+        let ranks: Vec<Rank> = match hand {
+            PokerHand::HighCard => {
+                let len = self.rng.range(1, 5);
+                self.distinct_ranks(len)
+            }
+            PokerHand::Pair => {
+                let kickers = self.rng.range(0, 3);
+                let r = self.distinct_ranks(kickers + 1);
+                [vec![r[0]; 2], r[1..].to_vec()].concat()
+            }
+            PokerHand::TwoPair => {
+                let kickers = self.rng.range(0, 1);
+                let r = self.distinct_ranks(kickers + 2);
+                [vec![r[0]; 2], vec![r[1]; 2], r[2..].to_vec()].concat()
+            }
+            PokerHand::ThreeOfAKind => {
+                let kickers = self.rng.range(0, 2);
+                let r = self.distinct_ranks(kickers + 1);
+                [vec![r[0]; 3], r[1..].to_vec()].concat()
+            }
+            PokerHand::FourOfAKind => {
+                let kickers = self.rng.range(0, 1);
+                let r = self.distinct_ranks(kickers + 1);
+                [vec![r[0]; 4], r[1..].to_vec()].concat()
+            }
+            PokerHand::FullHouse | PokerHand::FlushHouse => {
+                let r = self.distinct_ranks(2);
+                [vec![r[0]; 3], vec![r[1]; 2]].concat()
+            }
+            PokerHand::FiveOfAKind | PokerHand::FlushFive => vec![self.rng.choose(&self.ranks); 5],
+            PokerHand::Straight | PokerHand::StraightFlush => self.straight_ranks(),
+            PokerHand::Flush => self.distinct_ranks(5),
+        };
+
+        let flush = matches!(
+            hand,
+            PokerHand::Flush
+                | PokerHand::StraightFlush
+                | PokerHand::FlushHouse
+                | PokerHand::FlushFive
+        );
+        let flush_suit = self.rng.choose(&self.suits);
+        let mut cards: Vec<Card> = ranks
+            .into_iter()
+            .map(|rank| {
+                let suit = if flush {
+                    flush_suit
+                } else {
+                    self.rng.choose(&self.suits)
+                };
+                Card::new(rank, suit, None, None)
+            })
+            .collect();
+        self.rng.shuffle(&mut cards);
+        cards
+    }
+}
@@ -1,6 +1,5 @@
-use std::vec;
-
-use crate::HandValue;
+use super::catalog;
+use super::pokerhand::HandValue;
 use ortalib::{Card, Joker, JokerCard, PokerHand};
 
 #[derive(Debug)]
@@ -21,7 +20,7 @@ impl HandJoker {
         counts.sort();
 
         let origin_data = Self {
-            hand: handvalue.hand.clone(),
+            hand: handvalue.hand,
             cards_impl: handvalue.cards_impl.clone(),
             cards_hold_in_hand: handvalue.cards_hold_in_hand.clone(),
             joker_card: handvalue.joker_cards.clone(),
@@ -48,45 +47,67 @@ impl HandJoker {
         match joker {
             Joker::Joker | Joker::AbstractJoker => true,
             Joker::JollyJoker | Joker::SlyJoker => {
-                if data.counts.last() == Some(&5)
+                data.counts.last() == Some(&5)
                     || data.counts.last() == Some(&4)
                     || data.counts.last() == Some(&3)
                     || data.counts.last() == Some(&2)
-                {
-                    true
-                } else {
-                    false
-                }
             }
             Joker::ZanyJoker | Joker::WilyJoker => {
-                if data.counts.last() == Some(&5)
+                data.counts.last() == Some(&5)
                     || data.counts.last() == Some(&4)
                     || data.counts.last() == Some(&3)
-                {
-                    true
-                } else {
-                    false
-                }
             }
+            // A Full House contains a Two Pair too.
             Joker::MadJoker | Joker::CleverJoker => {
-                if data.counts == vec![2, 2] || data.counts == vec![1, 2, 2] {
-                    true
-                } else {
-                    false
-                }
+                data.counts.iter().filter(|&&n| n >= 2).count() >= 2
             }
             Joker::CrazyJoker | Joker::DeviousJoker => {
-                if data.hand == PokerHand::Straight || data.hand == PokerHand::StraightFlush {
-                    true
-                } else {
-                    false
-                }
-            }
-            Joker::DrollJoker | Joker::CraftyJoker => {
-                let groups = HandValue::is_flush(&data.cards_impl);
-                if groups { true } else { false }
+                data.hand == PokerHand::Straight || data.hand == PokerHand::StraightFlush
             }
-            _ => true,
+            // Not `is_flush` on the scoring cards: one High Card or a
+            // same-suit Pair would pass that.
+            Joker::DrollJoker | Joker::CraftyJoker => matches!(
+                data.hand,
+                PokerHand::Flush
+                    | PokerHand::StraightFlush
+                    | PokerHand::FlushHouse
+                    | PokerHand::FlushFive
+            ),
+            joker => catalog::is_implemented(*joker),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether `joker` takes effect when `played` is played.
+    fn works(played: &[&str], joker: Joker) -> bool {
+        let cards: Vec<Card> = played.iter().map(|c| c.parse().unwrap()).collect();
+        let jokers = [JokerCard::new(joker, None)];
+        let hand = HandJoker::analyze(&HandValue::evaluation(&cards, &[], &jokers));
+        !hand.work_joker_cards_in_hand.is_empty()
+    }
+
+    #[test]
+    fn droll_and_crafty_need_a_flush_hand() {
+        for joker in [Joker::DrollJoker, Joker::CraftyJoker] {
+            assert!(works(&["2♠", "4♠", "6♠", "8♠", "10♠"], joker));
+            assert!(works(&["2♠", "3♠", "4♠", "5♠", "6♠"], joker));
+            assert!(!works(&["A♠"], joker));
+            assert!(!works(&["A♠", "A♠"], joker));
+        }
+    }
+
+    #[test]
+    fn mad_and_clever_see_the_two_pair_in_a_full_house() {
+        for joker in [Joker::MadJoker, Joker::CleverJoker] {
+            assert!(works(&["K♠", "K♥", "2♠", "2♥"], joker));
+            assert!(works(&["K♠", "K♥", "2♠", "2♥", "5♦"], joker));
+            assert!(works(&["K♠", "K♥", "K♦", "2♠", "2♥"], joker));
+            assert!(!works(&["K♠", "K♥", "K♦", "2♠"], joker));
+            assert!(!works(&["K♠", "K♥", "K♦", "K♣"], joker));
         }
     }
 }
@@ -0,0 +1,61 @@
+use super::sorce::Sorce;
+use enum_iterator::cardinality;
+use ortalib::{Chips, Mult, PokerHand};
+
+/// Chips and mult each level above 1 adds to a hand's base values.
+#[rustfmt::skip]
+pub fn level_bonus(hand: PokerHand) -> (Chips, Mult) {
+    match hand {
+        PokerHand::HighCard =>      (10.0, 1.0),
+        PokerHand::Pair =>          (15.0, 1.0),
+        PokerHand::TwoPair =>       (20.0, 1.0),
+        PokerHand::ThreeOfAKind =>  (20.0, 2.0),
+        PokerHand::Straight =>      (30.0, 3.0),
+        PokerHand::Flush =>         (15.0, 2.0),
+        PokerHand::FullHouse =>     (25.0, 2.0),
+        PokerHand::FourOfAKind =>   (30.0, 3.0),
+        PokerHand::StraightFlush => (40.0, 4.0),
+        PokerHand::FiveOfAKind =>   (35.0, 3.0),
+        PokerHand::FlushHouse =>    (40.0, 4.0),
+        PokerHand::FlushFive =>     (50.0, 3.0),
+    }
+}
+
+/// The level of every poker hand, all starting at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandLevels {
+    levels: Vec<u32>,
+}
+
+impl Default for HandLevels {
+    fn default() -> Self {
+        Self {
+            levels: vec![1; cardinality::<PokerHand>()],
+        }
+    }
+}
+
+impl HandLevels {
+    pub fn level(&self, hand: PokerHand) -> u32 {
+        self.levels[hand as usize]
+    }
+
+    pub fn level_up(&mut self, hand: PokerHand) {
+        self.levels[hand as usize] += 1;
+    }
+
+    /// The hand's base chips and mult at its current level.
+    pub fn base(&self, hand: PokerHand) -> (Chips, Mult) {
+        let (chips, mult) = hand.hand_value();
+        let (bonus_chips, bonus_mult) = level_bonus(hand);
+        let extra = f64::from(self.level(hand) - 1);
+        (chips + bonus_chips * extra, mult + bonus_mult * extra)
+    }
+
+    /// Rescores `sorce` from the levelled base values. The scoring steps
+    /// do not depend on the base, so they are replayed unchanged.
+    pub fn apply(&self, sorce: &mut Sorce) {
+        (sorce.card_chips, sorce.base_mult) = self.base(sorce.hand);
+        (sorce.total_chips, sorce.mult) = sorce.replay::<f64>();
+    }
+}
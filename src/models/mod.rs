@@ -1,3 +1,24 @@
 pub mod pokerhand;
 pub mod sorce;
-pub mod jokers;
\ No newline at end of file
+pub mod jokers;
+pub mod rng;
+pub mod generator;
+pub mod round;
+pub mod oracle;
+pub mod shrink;
+pub mod numbers;
+pub mod catalog;
+pub mod session;
+pub mod solver;
+pub mod attribution;
+pub mod deck;
+pub mod blind;
+pub mod ante;
+pub mod advisor;
+pub mod consumables;
+pub mod economy;
+pub mod shop;
+pub mod levels;
+pub mod run;
+pub mod strategy;
+pub mod seed;
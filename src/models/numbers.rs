@@ -0,0 +1,365 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul};
+
+/// Scores at or above this switch to `e` notation, as the game does.
+pub const E_SWITCH_POINT: f64 = 1e11;
+
+/// Largest magnitude below which every integer is exactly representable in
+/// an `f64`.
+pub const EXACT_LIMIT: f64 = 9_007_199_254_740_992.0;
+
+/// Bits the mantissa of a [`BigScore`] may drift from 1 in either direction
+/// before it is rescaled; small enough that the product of two mantissas
+/// stays finite.
+const SCALE_BITS: i64 = 256;
+
+/// Exponent gap past which the smaller addend is too small to change the
+/// larger one's mantissa at all.
+const NEGLIGIBLE_BITS: i64 = 600;
+
+/// A non-negative number stored as `mantissa * 2^exponent`. Rescaling by a
+/// power of two is exact, so every operation rounds exactly where plain
+/// `f64` arithmetic would, while the `i64` exponent keeps going where `f64`
+/// overflows past `1.8e308`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BigScore {
+    mantissa: f64,
+    exponent: i64,
+    /// Whether any operation that produced this value had to round.
+    rounded: bool,
+}
+
+/// `2^exponent` for `|exponent| <= SCALE_BITS`, which is always exact.
+fn pow2(exponent: i64) -> f64 {
+    2f64.powi(exponent as i32)
+}
+
+impl BigScore {
+    pub const ZERO: Self = Self {
+        mantissa: 0.0,
+        exponent: 0,
+        rounded: false,
+    };
+
+    fn normalized(mut mantissa: f64, mut exponent: i64, rounded: bool) -> Self {
+        if mantissa == 0.0 {
+            return Self {
+                rounded,
+                ..Self::ZERO
+            };
+        }
+        while mantissa.abs() >= pow2(SCALE_BITS) {
+            mantissa *= pow2(-SCALE_BITS);
+            exponent += SCALE_BITS;
+        }
+        while mantissa.abs() < pow2(-SCALE_BITS) {
+            mantissa *= pow2(SCALE_BITS);
+            exponent -= SCALE_BITS;
+        }
+        Self {
+            mantissa,
+            exponent,
+            rounded,
+        }
+    }
+
+    /// The value as an `f64`; infinite once it is past the `f64` range.
+    pub fn to_f64(self) -> f64 {
+        let mut value = self.mantissa;
+        let mut exponent = self.exponent;
+        while exponent != 0 && value.is_finite() && value != 0.0 {
+            let step = exponent.clamp(-SCALE_BITS, SCALE_BITS);
+            value *= pow2(step);
+            exponent -= step;
+        }
+        value
+    }
+
+    pub fn floor(self) -> Self {
+        let value = self.to_f64();
+        if value.abs() >= EXACT_LIMIT {
+            // No fractional bits are left in the mantissa.
+            self
+        } else {
+            Self::normalized(value.floor(), 0, self.rounded)
+        }
+    }
+
+    /// Whether any step of the arithmetic rounded, so digits past the
+    /// 16th may be wrong.
+    pub fn is_approximate(self) -> bool {
+        self.rounded
+    }
+
+    /// The value as `mantissa * 10^exponent` with the mantissa in
+    /// `[1, 10)`, for display only.
+    fn decimal(self) -> (f64, i64) {
+        let log = self.mantissa.abs().log10() + self.exponent as f64 * 2f64.log10();
+        let exponent = log.floor();
+        (10f64.powf(log - exponent), exponent as i64)
+    }
+
+    /// The score as the game shows it, like [`game_format`] but without
+    /// ever reaching `naneinf`.
+    pub fn game_format(self) -> String {
+        let value = self.to_f64();
+        if value.is_finite() {
+            game_format(value)
+        } else {
+            let (mantissa, exponent) = self.decimal();
+            e_notation(mantissa, exponent)
+        }
+    }
+}
+
+impl From<f64> for BigScore {
+    fn from(value: f64) -> Self {
+        Self::normalized(value, 0, false)
+    }
+}
+
+impl Add for BigScore {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let rounded = self.rounded || rhs.rounded;
+        if self.mantissa == 0.0 {
+            return Self { rounded, ..rhs };
+        }
+        if rhs.mantissa == 0.0 {
+            return Self { rounded, ..self };
+        }
+        let (big, small) = if self.exponent >= rhs.exponent {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let gap = big.exponent - small.exponent;
+        if gap > NEGLIGIBLE_BITS {
+            return Self {
+                rounded: true,
+                ..big
+            };
+        }
+        // Both mantissas stay normal numbers after the shift, so it is exact.
+        let mut small_mantissa = small.mantissa;
+        let mut shift = gap;
+        while shift > 0 {
+            let step = shift.min(SCALE_BITS);
+            small_mantissa *= pow2(-step);
+            shift -= step;
+        }
+        let sum = big.mantissa + small_mantissa;
+        // Two-sum: the rounding error of the addition, exactly.
+        let back = sum - big.mantissa;
+        let error = (big.mantissa - (sum - back)) + (small_mantissa - back);
+        Self::normalized(sum, big.exponent, rounded || error != 0.0)
+    }
+}
+
+impl Mul for BigScore {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let product = self.mantissa * rhs.mantissa;
+        // A fused multiply-add recovers the rounding error of the product.
+        let error = self.mantissa.mul_add(rhs.mantissa, -product);
+        Self::normalized(
+            product,
+            self.exponent + rhs.exponent,
+            self.rounded || rhs.rounded || error != 0.0,
+        )
+    }
+}
+
+/// Plain digits while the value fits an `f64`, as the default output
+/// prints them, and `mantissa e exponent` past that.
+impl Display for BigScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = self.to_f64();
+        if value.is_finite() {
+            write!(f, "{value}")
+        } else {
+            let (mantissa, exponent) = self.decimal();
+            write!(f, "{mantissa}e{exponent}")
+        }
+    }
+}
+
+/// Formats a score the way the game displays it: comma grouping below
+/// [`E_SWITCH_POINT`], `e` notation above it and `naneinf` once the value has
+/// overflowed.
+pub fn game_format(score: f64) -> String {
+    if !score.is_finite() {
+        return "naneinf".to_string();
+    }
+    if score >= E_SWITCH_POINT {
+        let exponent = score.log10().floor() as i64;
+        return e_notation(score / 10f64.powi(exponent as i32), exponent);
+    }
+
+    let digits = format!("{:.0}", score.floor());
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Decimals the game shows in `e` notation: `1.234e11`.
+const E_DECIMALS: usize = 3;
+
+/// `1.234e11`: the mantissa rounded to [`E_DECIMALS`] places, carrying
+/// into the exponent when it rounds up to 10.
+fn e_notation(mantissa: f64, exponent: i64) -> String {
+    let (mut mantissa, mut exponent) = (mantissa, exponent);
+    // The caller's log10 can be off by one right at a power of ten.
+    if mantissa < 1.0 {
+        mantissa *= 10.0;
+        exponent -= 1;
+    }
+    let scale = 10f64.powi(E_DECIMALS as i32);
+    mantissa = (mantissa * scale).round() / scale;
+    if mantissa >= 10.0 {
+        mantissa /= 10.0;
+        exponent += 1;
+    }
+    format!("{mantissa:.E_DECIMALS$}e{exponent}")
+}
+
+/// An exact rational score. Every constant the engine applies (`1.5`, `2`,
+/// `+30`, ...) is a finite `f64` and so converts without rounding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exact(pub BigRational);
+
+impl Exact {
+    pub fn floor(&self) -> BigInt {
+        self.0.floor().to_integer()
+    }
+
+    /// Whether `value`, floored, is the same integer as this score floored.
+    pub fn floor_matches(&self, value: f64) -> bool {
+        BigRational::from_float(value.floor()).is_some_and(|v| v.to_integer() == self.floor())
+    }
+}
+
+impl From<f64> for Exact {
+    fn from(value: f64) -> Self {
+        Self(BigRational::from_float(value).expect("scoring constants are finite"))
+    }
+}
+
+impl Add for Exact {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Mul for Exact {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl Display for Exact {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The op mix scoring uses, applied to both number types.
+    fn mix<T: From<f64> + Add<Output = T> + Mul<Output = T> + Copy>(start: f64) -> T {
+        let mut value = T::from(start);
+        for i in 0..40 {
+            value = match i % 3 {
+                0 => value + T::from(10.0),
+                1 => value * T::from(1.5),
+                _ => value + T::from(0.1),
+            };
+        }
+        value
+    }
+
+    #[test]
+    fn big_score_rounds_exactly_like_f64_while_in_range() {
+        for start in [1.0, 3.0, 7.25, 1234.5] {
+            let plain: f64 = mix(start);
+            let big: BigScore = mix(start);
+            assert_eq!(big.to_f64(), plain, "start {start}");
+        }
+        let chips = BigScore::from(108.0);
+        let mult = BigScore::from(34.1875);
+        assert_eq!(
+            (chips * mult).floor().to_f64(),
+            (108.0f64 * 34.1875).floor()
+        );
+    }
+
+    #[test]
+    fn big_score_is_approximate_only_when_arithmetic_rounds() {
+        let mut power_of_two = BigScore::from(1.0);
+        let mut power_of_three = BigScore::from(1.0);
+        for _ in 0..80 {
+            power_of_two = power_of_two * BigScore::from(2.0);
+            power_of_three = power_of_three * BigScore::from(3.0);
+        }
+        assert!(!power_of_two.is_approximate());
+        assert_eq!(power_of_two.to_f64(), 2f64.powi(80));
+        assert!(power_of_three.is_approximate());
+        assert!(!(BigScore::from(3692.0) * BigScore::from(1.5)).is_approximate());
+        assert!((BigScore::from(0.1) + BigScore::from(0.2)).is_approximate());
+    }
+
+    #[test]
+    fn big_score_goes_past_f64_range() {
+        let huge = BigScore::from(1e300) * BigScore::from(1e10);
+        assert_eq!(huge.to_f64(), f64::INFINITY);
+        assert_eq!(huge.game_format(), "1.000e310");
+        assert_eq!((huge + BigScore::from(1.0)).game_format(), "1.000e310");
+        assert_eq!(game_format(1e300 * 1e10), "naneinf");
+    }
+
+    #[test]
+    fn big_score_displays_plain_digits() {
+        assert_eq!(BigScore::from(3692.0).to_string(), "3692");
+        assert_eq!(BigScore::from(3692.0).game_format(), "3,692");
+        assert_eq!(BigScore::from(2.75).floor().to_string(), "2");
+    }
+
+    #[test]
+    fn game_format_switches_to_e_notation_at_1e11() {
+        assert_eq!(game_format(0.0), "0");
+        assert_eq!(game_format(1234.9), "1,234");
+        assert_eq!(game_format(99_999_999_999.0), "99,999,999,999");
+        assert_eq!(game_format(E_SWITCH_POINT), "1.000e11");
+        assert_eq!(game_format(123_456_789_012.0), "1.235e11");
+        assert_eq!(game_format(9.9996e11), "1.000e12");
+        assert_eq!(game_format(9.08e19), "9.080e19");
+        assert_eq!(game_format(1e100), "1.000e100");
+        assert_eq!(game_format(f64::INFINITY), "naneinf");
+    }
+
+    #[test]
+    fn exact_keeps_every_digit() {
+        let sum = Exact::from(0.1) + Exact::from(0.2);
+        assert!(sum.floor_matches(0.1 + 0.2));
+        assert_ne!(sum, Exact::from(0.1 + 0.2));
+        let product = Exact::from(3.0) * Exact::from(1.5);
+        assert_eq!(product.floor(), BigInt::from(4));
+        assert!(product.floor_matches(4.5));
+        assert!(!product.floor_matches(5.0));
+    }
+}
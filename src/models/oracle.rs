@@ -0,0 +1,448 @@
+//! A second scorer written straight from the rules text in `ortalib`'s docs.
+//!
+//! It shares nothing with `HandValue`/`HandJoker`/`Sorce` on purpose: it
+//! re-derives the poker hand, walks the scoring steps in the order the rules
+//! list them and favours obviousness over speed, so `crosscheck` can use it to
+//! catch ordering mistakes in the real engine.
+
+use enum_iterator::all;
+use ortalib::{Card, Chips, Edition, Enhancement, Joker, Mult, PokerHand, Rank, Round, Suit};
+
+struct Rules {
+    four_fingers: bool,
+    shortcut: bool,
+    pareidolia: bool,
+    splash: bool,
+    smeared: bool,
+}
+
+pub fn score(round: &Round) -> (Chips, Mult) {
+    let jokers: Vec<Joker> = round.jokers.iter().map(|j| j.joker).collect();
+    let rules = Rules {
+        four_fingers: jokers.contains(&Joker::FourFingers),
+        shortcut: jokers.contains(&Joker::Shortcut),
+        pareidolia: jokers.contains(&Joker::Pareidolia),
+        splash: jokers.contains(&Joker::Splash),
+        smeared: jokers.contains(&Joker::SmearedJoker),
+    };
+    let effective = effective_jokers(&jokers);
+    let played = &round.cards_played;
+    let held = &round.cards_held_in_hand;
+
+    let flush = flush_cards(played, &rules);
+    let straight = straight_cards(played, &rules);
+    let (hand, mut scoring) = classify(played, &flush, &straight);
+    if rules.splash {
+        scoring = (0..played.len()).collect();
+    }
+
+    let counts = rank_counts(played);
+    let contains_pair = counts.iter().any(|(_, n)| *n >= 2);
+    let contains_three = counts.iter().any(|(_, n)| *n >= 3);
+    let contains_two_pair = counts.iter().filter(|(_, n)| *n >= 2).count() >= 2;
+    let contains_straight = straight.is_some();
+    let contains_flush = flush.is_some();
+
+    let (mut chips, mut mult) = hand.hand_value();
+    let is_face = |card: &Card| rules.pareidolia || card.rank.is_face();
+
+    // 1. Every scoring card, left to right.
+    let first_face = scoring.iter().copied().find(|&i| is_face(&played[i]));
+    for &i in &scoring {
+        let card = &played[i];
+        let retriggers = if is_face(card) {
+            count(&effective, Joker::SockAndBuskin)
+        } else {
+            0
+        };
+        for _ in 0..=retriggers {
+            chips += card.rank.rank_value();
+            match card.enhancement {
+                Some(Enhancement::Bonus) => chips += 30.0,
+                Some(Enhancement::Mult) => mult += 4.0,
+                Some(Enhancement::Glass) => mult *= 2.0,
+                _ => {}
+            }
+            match card.edition {
+                Some(Edition::Foil) => chips += 50.0,
+                Some(Edition::Holographic) => mult += 10.0,
+                Some(Edition::Polychrome) => mult *= 1.5,
+                None => {}
+            }
+            for joker in effective.iter().flatten() {
+                match joker {
+                    Joker::GreedyJoker if has_suit(card, Suit::Diamonds, &rules) => mult += 3.0,
+                    Joker::LustyJoker if has_suit(card, Suit::Hearts, &rules) => mult += 3.0,
+                    Joker::WrathfulJoker if has_suit(card, Suit::Spades, &rules) => mult += 3.0,
+                    Joker::GluttonousJoker if has_suit(card, Suit::Clubs, &rules) => mult += 3.0,
+                    Joker::Fibonacci
+                        if matches!(
+                            card.rank,
+                            Rank::Ace | Rank::Two | Rank::Three | Rank::Five | Rank::Eight
+                        ) =>
+                    {
+                        mult += 8.0
+                    }
+                    Joker::ScaryFace if is_face(card) => chips += 30.0,
+                    Joker::EvenSteven
+                        if matches!(
+                            card.rank,
+                            Rank::Ten | Rank::Eight | Rank::Six | Rank::Four | Rank::Two
+                        ) =>
+                    {
+                        mult += 4.0
+                    }
+                    Joker::OddTodd
+                        if matches!(
+                            card.rank,
+                            Rank::Ace | Rank::Nine | Rank::Seven | Rank::Five | Rank::Three
+                        ) =>
+                    {
+                        chips += 31.0
+                    }
+                    Joker::Photograph if first_face == Some(i) => mult *= 2.0,
+                    Joker::SmileyFace if is_face(card) => mult += 5.0,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // 2. Every card held in hand, left to right.
+    let lowest = lowest_held(held);
+    for (i, card) in held.iter().enumerate() {
+        for _ in 0..=count(&effective, Joker::Mime) {
+            if card.enhancement == Some(Enhancement::Steel) {
+                mult *= 1.5;
+            }
+            for joker in effective.iter().flatten() {
+                match joker {
+                    Joker::Baron if card.rank == Rank::King => mult *= 1.5,
+                    Joker::RaisedFist if lowest == Some(i) => mult += 2.0 * card.rank.rank_value(),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // 3. Every joker, left to right: Foil/Holographic, the joker, Polychrome.
+    for (joker_card, joker) in round.jokers.iter().zip(&effective) {
+        match joker_card.edition {
+            Some(Edition::Foil) => chips += 50.0,
+            Some(Edition::Holographic) => mult += 10.0,
+            _ => {}
+        }
+        match joker {
+            Some(Joker::Joker) => mult += 4.0,
+            Some(Joker::JollyJoker) if contains_pair => mult += 8.0,
+            Some(Joker::ZanyJoker) if contains_three => mult += 12.0,
+            Some(Joker::MadJoker) if contains_two_pair => mult += 10.0,
+            Some(Joker::CrazyJoker) if contains_straight => mult += 12.0,
+            Some(Joker::DrollJoker) if contains_flush => mult += 10.0,
+            Some(Joker::SlyJoker) if contains_pair => chips += 50.0,
+            Some(Joker::WilyJoker) if contains_three => chips += 100.0,
+            Some(Joker::CleverJoker) if contains_two_pair => chips += 80.0,
+            Some(Joker::DeviousJoker) if contains_straight => chips += 100.0,
+            Some(Joker::CraftyJoker) if contains_flush => chips += 80.0,
+            Some(Joker::AbstractJoker) => mult += 3.0 * round.jokers.len() as f64,
+            Some(Joker::Blackboard)
+                if held.iter().all(|c| {
+                    has_suit(c, Suit::Spades, &rules) || has_suit(c, Suit::Clubs, &rules)
+                }) =>
+            {
+                mult *= 3.0
+            }
+            Some(Joker::FlowerPot) => {
+                let cards: Vec<&Card> = scoring.iter().map(|&i| &played[i]).collect();
+                if covers_all_suits(&cards, &all::<Suit>().collect::<Vec<_>>(), &rules) {
+                    mult *= 3.0
+                }
+            }
+            _ => {}
+        }
+        if joker_card.edition == Some(Edition::Polychrome) {
+            mult *= 1.5;
+        }
+    }
+
+    (chips, mult)
+}
+
+/// What each joker slot actually does once Blueprint copies to its right.
+/// Jokers that only change the rules (Four Fingers etc.) cannot be copied.
+fn effective_jokers(jokers: &[Joker]) -> Vec<Option<Joker>> {
+    let mut effective = vec![None; jokers.len()];
+    for i in (0..jokers.len()).rev() {
+        effective[i] = match jokers[i] {
+            Joker::Blueprint => effective.get(i + 1).copied().flatten(),
+            Joker::FourFingers
+            | Joker::Shortcut
+            | Joker::Pareidolia
+            | Joker::Splash
+            | Joker::SmearedJoker => None,
+            joker => Some(joker),
+        };
+    }
+    effective
+}
+
+fn count(effective: &[Option<Joker>], joker: Joker) -> usize {
+    effective.iter().filter(|j| **j == Some(joker)).count()
+}
+
+fn has_suit(card: &Card, suit: Suit, rules: &Rules) -> bool {
+    card.enhancement == Some(Enhancement::Wild)
+        || card.suit == suit
+        || (rules.smeared && card.suit.color() == suit.color())
+}
+
+/// Lowest ranked held card; ties go to the rightmost one.
+fn lowest_held(held: &[Card]) -> Option<usize> {
+    let mut lowest: Option<usize> = None;
+    for (i, card) in held.iter().enumerate() {
+        if lowest.is_none_or(|l| card.rank <= held[l].rank) {
+            lowest = Some(i);
+        }
+    }
+    lowest
+}
+
+fn covers_all_suits(cards: &[&Card], suits: &[Suit], rules: &Rules) -> bool {
+    let Some((&suit, rest)) = suits.split_first() else {
+        return true;
+    };
+    (0..cards.len()).any(|i| {
+        if !has_suit(cards[i], suit, rules) {
+            return false;
+        }
+        let mut others = cards.to_vec();
+        others.remove(i);
+        covers_all_suits(&others, rest, rules)
+    })
+}
+
+fn rank_counts(cards: &[Card]) -> Vec<(Rank, usize)> {
+    let mut counts: Vec<(Rank, usize)> = Vec::new();
+    for card in cards {
+        match counts.iter_mut().find(|(r, _)| *r == card.rank) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((card.rank, 1)),
+        }
+    }
+    counts
+}
+
+fn indices_of_rank(cards: &[Card], rank: Rank) -> Vec<usize> {
+    (0..cards.len())
+        .filter(|&i| cards[i].rank == rank)
+        .collect()
+}
+
+/// Indices of the cards forming a flush, if any suit is shared by enough cards.
+fn flush_cards(cards: &[Card], rules: &Rules) -> Option<Vec<usize>> {
+    let needed = if rules.four_fingers { 4 } else { 5 };
+    all::<Suit>()
+        .map(|suit| {
+            (0..cards.len())
+                .filter(|&i| has_suit(&cards[i], suit, rules))
+                .collect::<Vec<_>>()
+        })
+        .filter(|idx| idx.len() >= needed)
+        .max_by_key(|idx| idx.len())
+}
+
+/// Indices of the largest subset of cards forming a straight, if any.
+///
+/// A straight needs distinct ranks, and adding a rank that lies between two
+/// others never breaks a run, so the longest run is a whole stretch of the
+/// sorted distinct ranks with no gap wider than allowed. Each rank is
+/// represented by its first card. Ties go to the stretch whose cards come
+/// earliest, comparing the last card first.
+fn straight_cards(cards: &[Card], rules: &Rules) -> Option<Vec<usize>> {
+    let needed = if rules.four_fingers { 4 } else { 5 };
+    let widest_gap = if rules.shortcut { 2 } else { 1 };
+    let mut best: Option<Vec<usize>> = None;
+    for ace_low in [false, true] {
+        let mut firsts: Vec<(u8, usize)> = Vec::new();
+        for (i, card) in cards.iter().enumerate() {
+            let value = rank_value(card.rank, ace_low);
+            if !firsts.iter().any(|&(v, _)| v == value) {
+                firsts.push((value, i));
+            }
+        }
+        firsts.sort_unstable();
+        let mut start = 0;
+        for end in 1..=firsts.len() {
+            if end < firsts.len() && firsts[end].0 - firsts[end - 1].0 <= widest_gap {
+                continue;
+            }
+            let mut idx: Vec<usize> = firsts[start..end].iter().map(|&(_, i)| i).collect();
+            idx.sort_unstable();
+            start = end;
+            let better = match &best {
+                None => true,
+                Some(b) => {
+                    idx.len() > b.len()
+                        || (idx.len() == b.len() && idx.iter().rev().lt(b.iter().rev()))
+                }
+            };
+            if idx.len() >= needed && better {
+                best = Some(idx);
+            }
+        }
+    }
+    best
+}
+
+fn rank_value(rank: Rank, ace_low: bool) -> u8 {
+    match rank {
+        Rank::Ace if ace_low => 1,
+        r => all::<Rank>().position(|x| x == r).unwrap() as u8 + 2,
+    }
+}
+
+/// Picks the highest poker hand and the indices of the cards that score,
+/// checking hands from the top of the table down.
+fn classify(
+    cards: &[Card],
+    flush: &Option<Vec<usize>>,
+    straight: &Option<Vec<usize>>,
+) -> (PokerHand, Vec<usize>) {
+    let everything: Vec<usize> = (0..cards.len()).collect();
+    let mut counts = rank_counts(cards);
+    counts.sort_by_key(|c| std::cmp::Reverse(c.1));
+    let shape: Vec<usize> = counts.iter().map(|(_, n)| *n).collect();
+    let whole_flush = flush.as_ref().is_some_and(|f| f.len() == cards.len()) && cards.len() == 5;
+
+    if shape == [5] && whole_flush {
+        return (PokerHand::FlushFive, everything);
+    }
+    if shape == [3, 2] && whole_flush {
+        return (PokerHand::FlushHouse, everything);
+    }
+    if shape == [5] {
+        return (PokerHand::FiveOfAKind, everything);
+    }
+    if let (Some(f), Some(s)) = (flush, straight) {
+        let mut both: Vec<usize> = f.iter().chain(s).copied().collect();
+        both.sort_unstable();
+        both.dedup();
+        return (PokerHand::StraightFlush, both);
+    }
+    if shape.first() == Some(&4) {
+        return (PokerHand::FourOfAKind, indices_of_rank(cards, counts[0].0));
+    }
+    if shape == [3, 2] {
+        return (PokerHand::FullHouse, everything);
+    }
+    if let Some(f) = flush {
+        return (PokerHand::Flush, f.clone());
+    }
+    if let Some(s) = straight {
+        return (PokerHand::Straight, s.clone());
+    }
+    if shape.first() == Some(&3) {
+        return (PokerHand::ThreeOfAKind, indices_of_rank(cards, counts[0].0));
+    }
+    if shape.len() >= 2 && shape[0] == 2 && shape[1] == 2 {
+        let mut both = indices_of_rank(cards, counts[0].0);
+        both.extend(indices_of_rank(cards, counts[1].0));
+        both.sort_unstable();
+        return (PokerHand::TwoPair, both);
+    }
+    if shape.first() == Some(&2) {
+        return (PokerHand::Pair, indices_of_rank(cards, counts[0].0));
+    }
+    let highest = (0..cards.len())
+        .max_by(|&a, &b| cards[a].rank.cmp(&cards[b].rank).then(b.cmp(&a)))
+        .into_iter()
+        .collect();
+    (PokerHand::HighCard, highest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::rng::SeededRng;
+
+    fn rules(four_fingers: bool, shortcut: bool) -> Rules {
+        Rules {
+            four_fingers,
+            shortcut,
+            pareidolia: false,
+            splash: false,
+            smeared: false,
+        }
+    }
+
+    /// The definition itself: the first largest subset, in mask order,
+    /// whose ranks form a run.
+    fn brute_force_straight(cards: &[Card], rules: &Rules) -> Option<Vec<usize>> {
+        let needed = if rules.four_fingers { 4 } else { 5 };
+        let widest_gap = if rules.shortcut { 2 } else { 1 };
+        let is_run = |idx: &[usize], ace_low: bool| {
+            let mut values: Vec<u8> = idx
+                .iter()
+                .map(|&i| rank_value(cards[i].rank, ace_low))
+                .collect();
+            values.sort_unstable();
+            values
+                .windows(2)
+                .all(|w| (1..=widest_gap).contains(&(w[1] - w[0])))
+        };
+        let mut best: Option<Vec<usize>> = None;
+        for mask in 0u32..(1 << cards.len()) {
+            let idx: Vec<usize> = (0..cards.len()).filter(|i| mask & (1 << i) != 0).collect();
+            if idx.len() < needed || best.as_ref().is_some_and(|b| b.len() >= idx.len()) {
+                continue;
+            }
+            if is_run(&idx, false) || is_run(&idx, true) {
+                best = Some(idx);
+            }
+        }
+        best
+    }
+
+    fn card(rank: Rank) -> Card {
+        Card::new(rank, Suit::Spades, None, None)
+    }
+
+    #[test]
+    fn straights_match_the_subset_definition() {
+        let ranks: Vec<Rank> = all::<Rank>().collect();
+        let mut rng = SeededRng::new(7);
+        for _ in 0..2000 {
+            let len = rng.range(1, 8);
+            let cards: Vec<Card> = (0..len).map(|_| card(rng.choose(&ranks))).collect();
+            for (four_fingers, shortcut) in
+                [(false, false), (true, false), (false, true), (true, true)]
+            {
+                let rules = rules(four_fingers, shortcut);
+                assert_eq!(
+                    straight_cards(&cards, &rules),
+                    brute_force_straight(&cards, &rules),
+                    "{cards:?} four fingers {four_fingers} shortcut {shortcut}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn straights_of_many_cards_do_not_overflow() {
+        let cards: Vec<Card> = all::<Rank>().cycle().take(40).map(card).collect();
+        let straight = straight_cards(&cards, &rules(false, false)).unwrap();
+        assert_eq!(straight, (0..13).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn ace_plays_low_in_a_wheel() {
+        let cards: Vec<Card> = [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five]
+            .into_iter()
+            .map(card)
+            .collect();
+        assert_eq!(
+            straight_cards(&cards, &rules(false, false)),
+            Some(vec![0, 1, 2, 3, 4])
+        );
+    }
+}
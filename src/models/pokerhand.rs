@@ -2,6 +2,8 @@ use ortalib::{Card, Enhancement, JokerCard, PokerHand, Rank};
 use std::collections::{HashMap, HashSet};
 use std::vec;
 
+type HandCheck = fn(&[Card]) -> Option<HandValue>;
+
 #[derive(Debug)]
 pub struct HandValue {
     pub hand: PokerHand,
@@ -12,7 +14,7 @@ pub struct HandValue {
 
 impl HandValue {
     pub fn evaluation(cards: &[Card], hold_cards: &[Card], joker_card: &[JokerCard]) -> Self {
-        let check: Vec<fn(&[Card]) -> Option<Self>> = vec![
+        let check: Vec<HandCheck> = vec![
             Self::check_flush_five,
             Self::check_flush_house,
             Self::check_five_of_a_kind,
@@ -294,11 +296,12 @@ impl HandValue {
         counts.sort();
 
         if counts.iter().filter(|&&c| c == 2).count() == 2 {
-            let two_pairs: Vec<Card> = groups
-                .values()
-                .filter(|v| v.len() == 2)
-                .take(2)
-                .flat_map(|v| v.clone())
+            // In played order; the map's iteration order differs between
+            // processes and would change which card scores first.
+            let two_pairs: Vec<Card> = cards
+                .iter()
+                .filter(|c| groups[&c.rank].len() == 2)
+                .copied()
                 .collect();
 
             Some(Self {
@@ -0,0 +1,10 @@
+use ortalib::Round;
+
+/// `Round` does not derive `Clone`; the cards and jokers inside it are `Copy`.
+pub fn clone_round(round: &Round) -> Round {
+    Round {
+        cards_played: round.cards_played.clone(),
+        cards_held_in_hand: round.cards_held_in_hand.clone(),
+        jokers: round.jokers.clone(),
+    }
+}
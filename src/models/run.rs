@@ -0,0 +1,472 @@
+use super::ante::{BlindKind, Stake, required_score};
+use super::blind::{Blind, BlindConfig, BlindStatus};
+use super::consumables::{Planet, Tarot};
+use super::deck::{Deck, DeckKind};
+use super::economy::{self, RoundEnd, STARTING_MONEY};
+use super::levels::HandLevels;
+use super::rng::SeededRng;
+use super::seed::{Seed, Stream};
+use super::shop::{self, JOKER_SLOTS, Shop, ShopConfig};
+use ortalib::{Card, JokerCard};
+use std::fmt::{Display, Formatter};
+
+/// Beating this ante's Boss Blind wins the run.
+pub const WINNING_ANTE: u32 = 8;
+/// Tarots a player can hold at once.
+pub const CONSUMABLE_SLOTS: usize = 2;
+/// Price of a planet or tarot in the shop.
+pub const CONSUMABLE_COST: i64 = 3;
+/// Planets and tarots on offer next to the jokers.
+pub const CONSUMABLE_OFFERS: usize = 2;
+/// Shop actions allowed per visit, so a strategy cannot loop forever.
+pub const MAX_SHOP_ACTIONS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunConfig {
+    pub deck: DeckKind,
+    pub stake: Stake,
+    pub seed: Seed,
+    /// The ante whose Boss Blind ends the run as won; `None` is endless
+    /// mode, which goes on until a blind is lost.
+    pub last_ante: Option<u32>,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            deck: DeckKind::Standard,
+            stake: Stake::White,
+            seed: Seed::from_u64(0),
+            last_ante: Some(WINNING_ANTE),
+        }
+    }
+}
+
+/// Something for sale in the shop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offer {
+    Joker(JokerCard),
+    /// Levels up its hand as soon as it is bought.
+    Planet(Planet),
+    /// Held until the strategy uses it during a blind.
+    Tarot(Tarot),
+}
+
+impl Offer {
+    pub fn cost(&self) -> i64 {
+        match self {
+            Offer::Joker(card) => shop::cost(card),
+            Offer::Planet(_) | Offer::Tarot(_) => CONSUMABLE_COST,
+        }
+    }
+}
+
+/// What a strategy sees while playing a blind.
+#[derive(Debug, Clone, Copy)]
+pub struct BlindView<'a> {
+    pub ante: u32,
+    pub blind: BlindKind,
+    pub target: f64,
+    pub score: f64,
+    pub hand: &'a [Card],
+    pub hands_left: usize,
+    pub discards_left: usize,
+    pub jokers: &'a [JokerCard],
+    pub levels: &'a HandLevels,
+    pub tarots: &'a [Tarot],
+}
+
+/// Card indices are 0-based positions in `BlindView::hand`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlindAction {
+    Play(Vec<usize>),
+    Discard(Vec<usize>),
+    /// Uses the tarot at a position in `BlindView::tarots` on hand cards.
+    UseTarot(usize, Vec<usize>),
+}
+
+/// What a strategy sees in the shop after winning a blind.
+#[derive(Debug, Clone, Copy)]
+pub struct ShopView<'a> {
+    pub ante: u32,
+    pub money: i64,
+    pub offers: &'a [Offer],
+    pub reroll_cost: i64,
+    pub jokers: &'a [JokerCard],
+    pub joker_slots: usize,
+    pub tarots: &'a [Tarot],
+    pub levels: &'a HandLevels,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShopAction {
+    /// Buys the offer at a position in `ShopView::offers`.
+    Buy(usize),
+    /// Sells the joker at a position in `ShopView::jokers`.
+    Sell(usize),
+    Reroll,
+    Leave,
+}
+
+/// Decides every play, discard and purchase of a run. Actions the rules do
+/// not allow end the run with an error.
+pub trait Strategy {
+    fn name(&self) -> &str;
+    fn blind_action(&mut self, view: &BlindView) -> BlindAction;
+    fn shop_action(&mut self, view: &ShopView) -> ShopAction;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlindRecord {
+    pub ante: u32,
+    pub blind: BlindKind,
+    pub target: f64,
+    pub score: f64,
+    pub won: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport {
+    pub strategy: String,
+    pub config: RunConfig,
+    pub log: Vec<String>,
+    pub blinds: Vec<BlindRecord>,
+    /// Money held entering each blind, then at the end of the run.
+    pub money_curve: Vec<i64>,
+    /// Highest score of a single hand.
+    pub best_hand: f64,
+    pub won: bool,
+}
+
+impl RunReport {
+    pub fn furthest_ante(&self) -> u32 {
+        self.blinds.last().map_or(0, |b| b.ante)
+    }
+
+    pub fn blinds_won(&self) -> usize {
+        self.blinds.iter().filter(|b| b.won).count()
+    }
+
+    /// Score reached in the last blind played.
+    pub fn final_score(&self) -> f64 {
+        self.blinds.last().map_or(0.0, |b| b.score)
+    }
+
+    pub fn total_score(&self) -> f64 {
+        self.blinds.iter().map(|b| b.score).sum()
+    }
+}
+
+fn show(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A new set of planets and tarots. The Magician is left out since Lucky
+/// Cards are not modelled, as are planets for secret hands.
+fn consumable_offers(rng: &mut SeededRng) -> Vec<Offer> {
+    let planets: Vec<Planet> = Planet::ALL.into_iter().filter(|p| !p.is_secret()).collect();
+    let tarots: Vec<Tarot> = Tarot::ALL
+        .into_iter()
+        .filter(|&t| t != Tarot::TheMagician)
+        .collect();
+    (0..CONSUMABLE_OFFERS)
+        .map(|_| {
+            if rng.chance(0.5) {
+                Offer::Planet(rng.choose(&planets))
+            } else {
+                Offer::Tarot(rng.choose(&tarots))
+            }
+        })
+        .collect()
+}
+
+/// Everything that carries over from one blind to the next.
+struct RunState {
+    deck: Option<Deck>,
+    jokers: Vec<JokerCard>,
+    levels: HandLevels,
+    tarots: Vec<Tarot>,
+    money: i64,
+    /// Seeds each blind's Glass rolls.
+    glass: SeededRng,
+    /// Seeds each shop visit's offers.
+    shop: SeededRng,
+    report: RunReport,
+}
+
+impl RunState {
+    fn log(&mut self, line: String) {
+        self.report.log.push(line);
+    }
+
+    /// Plays one blind to the end and cashes out if it was won.
+    fn play_blind(
+        &mut self,
+        config: &RunConfig,
+        ante: u32,
+        kind: BlindKind,
+        strategy: &mut dyn Strategy,
+    ) -> Result<bool, String> {
+        let defaults = BlindConfig::default();
+        let deck = self
+            .deck
+            .take()
+            .expect("the deck is returned after each blind");
+        let hand_size = defaults.hand_size as i32 + deck.kind.hand_size_modifier();
+        let discards = defaults.discards as i32 + config.stake.discard_modifier();
+        let blind_config = BlindConfig {
+            target: required_score(ante, kind, config.stake),
+            hand_size: hand_size.max(1) as usize,
+            discards: discards.max(0) as usize,
+            ..defaults
+        };
+        let rng = SeededRng::new(self.glass.next_u64());
+        let mut blind = Blind::new(blind_config, deck, self.jokers.clone(), rng);
+        blind.levels = self.levels.clone();
+        self.report.money_curve.push(self.money);
+        self.log(format!(
+            "Ante {ante} {kind}: {} to win",
+            blind_config.target
+        ));
+
+        while blind.status() == BlindStatus::InProgress {
+            let view = BlindView {
+                ante,
+                blind: kind,
+                target: blind.config.target,
+                score: blind.score(),
+                hand: blind.hand(),
+                hands_left: blind.hands_left(),
+                discards_left: blind.discards_left(),
+                jokers: &self.jokers,
+                levels: &self.levels,
+                tarots: &self.tarots,
+            };
+            let action = strategy.blind_action(&view);
+            let context = |e: String| format!("Ante {ante} {kind}: {e}");
+            let picked = |indices: &[usize]| {
+                let cards: Vec<Card> = indices
+                    .iter()
+                    .filter_map(|&i| blind.hand().get(i).copied())
+                    .collect();
+                show(&cards)
+            };
+            match action {
+                BlindAction::Play(indices) => {
+                    let cards = picked(&indices);
+                    let sorce = blind.play(&indices).map_err(context)?;
+                    let hand_score = (sorce.total_chips * sorce.mult).floor();
+                    self.report.best_hand = self.report.best_hand.max(hand_score);
+                    self.log(format!(
+                        "  play {cards}: {} for {hand_score} ({}/{})",
+                        sorce.hand,
+                        blind.score(),
+                        blind.config.target
+                    ));
+                }
+                BlindAction::Discard(indices) => {
+                    let cards = picked(&indices);
+                    blind.discard(&indices).map_err(context)?;
+                    self.log(format!("  discard {cards}"));
+                }
+                BlindAction::UseTarot(slot, indices) => {
+                    let tarot = *self
+                        .tarots
+                        .get(slot)
+                        .ok_or_else(|| context(format!("there is no tarot #{}", slot + 1)))?;
+                    let cards = picked(&indices);
+                    let changed = blind.use_tarot(tarot, &indices).map_err(context)?;
+                    self.tarots.remove(slot);
+                    self.log(format!("  use {tarot}: {cards} -> {}", show(&changed)));
+                }
+            }
+        }
+
+        let won = blind.status() == BlindStatus::Won;
+        self.report.blinds.push(BlindRecord {
+            ante,
+            blind: kind,
+            target: blind.config.target,
+            score: blind.score(),
+            won,
+        });
+        if !won {
+            self.log(format!("  lost with {}", blind.score()));
+            self.deck = Some(blind.finish());
+            return Ok(false);
+        }
+
+        let full_deck = blind.full_deck();
+        let end = RoundEnd {
+            blind: kind,
+            stake: config.stake,
+            money: self.money,
+            hands_left: blind.hands_left(),
+            discards_left: blind.discards_left(),
+            discards_used: blind.discards_used(),
+            full_deck: &full_deck,
+        };
+        let earned = economy::total(&economy::payout(&end, &[]));
+        self.log(format!(
+            "  won with {}, cash out ${earned}: ${} -> ${}",
+            blind.score(),
+            self.money,
+            self.money + earned
+        ));
+        self.money += earned;
+        let mut deck = blind.finish();
+        deck.reshuffle();
+        self.deck = Some(deck);
+        Ok(true)
+    }
+
+    fn visit_shop(
+        &mut self,
+        config: &RunConfig,
+        ante: u32,
+        strategy: &mut dyn Strategy,
+    ) -> Result<(), String> {
+        let kind = self.deck.as_ref().map_or(config.deck, |d| d.kind);
+        let joker_slots = (JOKER_SLOTS as i32 + kind.joker_slot_modifier()).max(0) as usize;
+        let mut shop = Shop::new(
+            ShopConfig::default(),
+            &self.jokers,
+            SeededRng::new(self.shop.next_u64()),
+        );
+        let mut offer_rng = SeededRng::new(self.shop.next_u64());
+        let mut consumables = consumable_offers(&mut offer_rng);
+        let context = |e: String| format!("Ante {ante} shop: {e}");
+
+        for _ in 0..MAX_SHOP_ACTIONS {
+            let offers: Vec<Offer> = shop
+                .offers()
+                .iter()
+                .map(|&card| Offer::Joker(card))
+                .chain(consumables.iter().copied())
+                .collect();
+            let view = ShopView {
+                ante,
+                money: self.money,
+                offers: &offers,
+                reroll_cost: shop.reroll_cost(),
+                jokers: &self.jokers,
+                joker_slots,
+                tarots: &self.tarots,
+                levels: &self.levels,
+            };
+            match strategy.shop_action(&view) {
+                ShopAction::Buy(i) if i < shop.offers().len() => {
+                    let card = shop
+                        .buy(i, &mut self.jokers, joker_slots, &mut self.money)
+                        .map_err(context)?;
+                    self.log(format!("  buy {card} for ${}", shop::cost(&card)));
+                }
+                ShopAction::Buy(i) => {
+                    let j = i - shop.offers().len();
+                    let offer = *consumables
+                        .get(j)
+                        .ok_or_else(|| context(format!("there is no offer #{}", i + 1)))?;
+                    if self.money < CONSUMABLE_COST {
+                        return Err(context(format!(
+                            "it costs ${CONSUMABLE_COST}, you have ${}",
+                            self.money
+                        )));
+                    }
+                    match offer {
+                        Offer::Planet(planet) => {
+                            self.levels.level_up(planet.hand());
+                            self.log(format!(
+                                "  buy {planet}: {} to level {}",
+                                planet.hand(),
+                                self.levels.level(planet.hand())
+                            ));
+                        }
+                        Offer::Tarot(tarot) => {
+                            if self.tarots.len() >= CONSUMABLE_SLOTS {
+                                return Err(context(format!(
+                                    "all {CONSUMABLE_SLOTS} consumable slots are full"
+                                )));
+                            }
+                            self.tarots.push(tarot);
+                            self.log(format!("  buy {tarot}"));
+                        }
+                        Offer::Joker(_) => unreachable!("jokers are sold by `shop`"),
+                    }
+                    self.money -= CONSUMABLE_COST;
+                    consumables.remove(j);
+                }
+                ShopAction::Sell(i) => {
+                    let card = shop::sell(&mut self.jokers, i, &mut self.money).map_err(context)?;
+                    self.log(format!("  sell {card} for ${}", shop::sell_value(&card)));
+                }
+                ShopAction::Reroll => {
+                    let price = shop
+                        .reroll(&self.jokers, &mut self.money)
+                        .map_err(context)?;
+                    consumables = consumable_offers(&mut offer_rng);
+                    self.log(format!("  reroll for ${price}"));
+                }
+                ShopAction::Leave => {
+                    self.log(format!("  leave the shop with ${}", self.money));
+                    return Ok(());
+                }
+            }
+        }
+        Err(context(format!(
+            "the strategy did not leave after {MAX_SHOP_ACTIONS} actions"
+        )))
+    }
+}
+
+/// Plays a whole run: every blind of every ante, with a shop after each
+/// won blind. The same config and strategy always give the same run.
+pub fn simulate(config: &RunConfig, strategy: &mut dyn Strategy) -> Result<RunReport, String> {
+    let deck = Deck::new(config.deck, config.seed.stream_seed(Stream::Deck));
+    let mut state = RunState {
+        deck: Some(deck),
+        jokers: Vec::new(),
+        levels: HandLevels::default(),
+        tarots: Vec::new(),
+        money: STARTING_MONEY,
+        glass: config.seed.rng(Stream::Glass),
+        shop: config.seed.rng(Stream::Shop),
+        report: RunReport {
+            strategy: strategy.name().to_string(),
+            config: *config,
+            log: Vec::new(),
+            blinds: Vec::new(),
+            money_curve: Vec::new(),
+            best_hand: 0.0,
+            won: false,
+        },
+    };
+
+    'run: for ante in 1.. {
+        for kind in BlindKind::ALL {
+            if !state.play_blind(config, ante, kind, strategy)? {
+                break 'run;
+            }
+            if kind == BlindKind::Boss && config.last_ante == Some(ante) {
+                state.report.won = true;
+                break 'run;
+            }
+            state.visit_shop(config, ante, strategy)?;
+        }
+    }
+    state.report.money_curve.push(state.money);
+    Ok(state.report)
+}
+
+impl Display for Offer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Offer::Joker(card) => write!(f, "{card}"),
+            Offer::Planet(planet) => write!(f, "{planet} ({})", planet.hand()),
+            Offer::Tarot(tarot) => write!(f, "{tarot}"),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use super::rng::SeededRng;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The characters the game uses in seeds: no 0 or O, which look alike.
+const ALPHABET: &[u8; 34] = b"123456789ABCDEFGHIJKLMNPQRSTUVWXYZ";
+pub const SEED_LENGTH: usize = 8;
+
+/// The parts of a simulation that draw random numbers. Each gets its own
+/// stream, so changing how often one draws leaves the others unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    /// Building and shuffling the deck.
+    Deck,
+    /// Joker, planet and tarot offers.
+    Shop,
+    /// Glass cards shattering.
+    Glass,
+    /// Spectral effects.
+    Consumables,
+    /// Strategies that choose at random.
+    Strategy,
+}
+
+/// An 8-character seed such as `7LB2WVPK`, which names a whole run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Seed([u8; SEED_LENGTH]);
+
+/// 64-bit FNV-1a, written out so stream seeds never change with the
+/// standard library's hasher.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+impl Seed {
+    /// How many distinct seeds there are.
+    pub const COUNT: u64 = 34u64.pow(SEED_LENGTH as u32);
+
+    /// The seed numbered `n`, wrapping around after `COUNT`.
+    pub fn from_u64(n: u64) -> Self {
+        let mut n = n % Self::COUNT;
+        let mut chars = [0; SEED_LENGTH];
+        for c in chars.iter_mut().rev() {
+            *c = ALPHABET[(n % 34) as usize];
+            n /= 34;
+        }
+        Seed(chars)
+    }
+
+    /// The inverse of `from_u64`.
+    pub fn to_u64(self) -> u64 {
+        self.0.iter().fold(0, |n, c| {
+            let digit = ALPHABET.iter().position(|a| a == c).expect("validated");
+            n * 34 + digit as u64
+        })
+    }
+
+    /// A fresh seed from the clock, for runs started without one.
+    pub fn random() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        Self::from_u64(SeededRng::new(nanos as u64).next_u64())
+    }
+
+    /// The number seeding `stream`.
+    pub fn stream_seed(self, stream: Stream) -> u64 {
+        fnv1a(format!("{self}/{stream}").as_bytes())
+    }
+
+    pub fn rng(self, stream: Stream) -> SeededRng {
+        SeededRng::new(self.stream_seed(stream))
+    }
+}
+
+impl Display for Stream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Stream::Deck => "deck",
+            Stream::Shop => "shop",
+            Stream::Glass => "glass",
+            Stream::Consumables => "consumables",
+            Stream::Strategy => "strategy",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Display for Seed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let chars = self.0.iter().map(|&c| char::from(c));
+        write!(f, "{}", chars.collect::<String>())
+    }
+}
+
+impl FromStr for Seed {
+    type Err = String;
+
+    /// Accepts exactly 8 characters from the game's alphabet, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.trim().to_ascii_uppercase();
+        let valid = upper.len() == SEED_LENGTH && upper.bytes().all(|b| ALPHABET.contains(&b));
+        if !valid {
+            return Err(format!("Invalid Seed: `{s}`"));
+        }
+        let mut chars = [0; SEED_LENGTH];
+        chars.copy_from_slice(upper.as_bytes());
+        Ok(Seed(chars))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_map_onto_the_alphabet() {
+        assert_eq!(Seed::from_u64(0).to_string(), "11111111");
+        assert_eq!(Seed::from_u64(1).to_string(), "11111112");
+        assert_eq!(Seed::from_u64(34).to_string(), "11111121");
+        assert_eq!(Seed::from_u64(Seed::COUNT - 1).to_string(), "ZZZZZZZZ");
+        assert_eq!(Seed::from_u64(Seed::COUNT), Seed::from_u64(0));
+    }
+
+    #[test]
+    fn from_u64_and_to_u64_round_trip() {
+        let mut rng = SeededRng::new(1);
+        for _ in 0..1000 {
+            let n = rng.next_u64() % Seed::COUNT;
+            assert_eq!(Seed::from_u64(n).to_u64(), n);
+        }
+    }
+
+    #[test]
+    fn parsing_takes_any_case_and_rejects_the_rest() {
+        let seed: Seed = "7lb2wvpk".parse().unwrap();
+        assert_eq!(seed.to_string(), "7LB2WVPK");
+        assert_eq!(seed.to_string().parse(), Ok(seed));
+        for bad in ["7LB2WVP", "7LB2WVPKX", "7LB2WVP0", "7LB2WVPO", "7LB2 VPK"] {
+            assert_eq!(bad.parse::<Seed>(), Err(format!("Invalid Seed: `{bad}`")));
+        }
+    }
+
+    #[test]
+    fn streams_get_different_seeds() {
+        let seed: Seed = "7LB2WVPK".parse().unwrap();
+        let streams = [
+            Stream::Deck,
+            Stream::Shop,
+            Stream::Glass,
+            Stream::Consumables,
+            Stream::Strategy,
+        ];
+        let seeds: Vec<u64> = streams.iter().map(|&s| seed.stream_seed(s)).collect();
+        for (i, a) in seeds.iter().enumerate() {
+            assert!(!seeds[i + 1..].contains(a));
+        }
+        assert_eq!(
+            seed.stream_seed(Stream::Deck),
+            seed.stream_seed(Stream::Deck)
+        );
+    }
+}
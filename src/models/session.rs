@@ -0,0 +1,257 @@
+use super::round::clone_round;
+use super::solver::MAX_PLAYED;
+use super::sorce::Sorce;
+use ortalib::{Card, Edition, Enhancement, JokerCard, Round};
+use std::panic;
+
+/// Which list a card index refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Played,
+    Held,
+}
+
+/// A round being edited by hand, with undo. Indices are 0-based here;
+/// front ends translate from whatever the user typed.
+pub struct Session {
+    pub round: Round,
+    history: Vec<Round>,
+}
+
+impl Session {
+    pub fn new(round: Round) -> Self {
+        Self {
+            round,
+            history: Vec::new(),
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        self.history.push(clone_round(&self.round));
+    }
+
+    fn cards_mut(&mut self, zone: Zone) -> &mut Vec<Card> {
+        match zone {
+            Zone::Played => &mut self.round.cards_played,
+            Zone::Held => &mut self.round.cards_held_in_hand,
+        }
+    }
+
+    fn check_index(len: usize, index: usize, what: &str) -> Result<(), String> {
+        if index < len {
+            Ok(())
+        } else {
+            Err(format!("no {what} #{} (there are {len})", index + 1))
+        }
+    }
+
+    /// Fails if the played cards would number more than `MAX_PLAYED`, the
+    /// most the game lets you play in one hand.
+    fn check_room(&mut self, zone: Zone, adding: usize) -> Result<(), String> {
+        let played = self.round.cards_played.len();
+        if zone == Zone::Played && played + adding > MAX_PLAYED {
+            return Err(format!(
+                "at most {MAX_PLAYED} cards may be played ({played} already are)"
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn add_cards(&mut self, zone: Zone, cards: &[Card]) -> Result<(), String> {
+        self.check_room(zone, cards.len())?;
+        self.checkpoint();
+        self.cards_mut(zone).extend_from_slice(cards);
+        Ok(())
+    }
+
+    pub fn remove_card(&mut self, zone: Zone, index: usize) -> Result<Card, String> {
+        Self::check_index(self.cards_mut(zone).len(), index, "card")?;
+        self.checkpoint();
+        Ok(self.cards_mut(zone).remove(index))
+    }
+
+    /// Moves a card to the end of the other zone.
+    pub fn move_card(&mut self, from: Zone, index: usize) -> Result<(), String> {
+        Self::check_index(self.cards_mut(from).len(), index, "card")?;
+        let to = match from {
+            Zone::Played => Zone::Held,
+            Zone::Held => Zone::Played,
+        };
+        self.check_room(to, 1)?;
+        self.checkpoint();
+        let card = self.cards_mut(from).remove(index);
+        self.cards_mut(to).push(card);
+        Ok(())
+    }
+
+    /// Replaces a card outright, e.g. to change its rank or suit.
+    pub fn set_card(&mut self, zone: Zone, index: usize, card: Card) -> Result<(), String> {
+        Self::check_index(self.cards_mut(zone).len(), index, "card")?;
+        self.checkpoint();
+        self.cards_mut(zone)[index] = card;
+        Ok(())
+    }
+
+    pub fn set_enhancement(
+        &mut self,
+        zone: Zone,
+        index: usize,
+        enhancement: Option<Enhancement>,
+    ) -> Result<(), String> {
+        Self::check_index(self.cards_mut(zone).len(), index, "card")?;
+        self.checkpoint();
+        let card = &mut self.cards_mut(zone)[index];
+        *card = Card::new(card.rank, card.suit, enhancement, card.edition);
+        Ok(())
+    }
+
+    pub fn set_edition(
+        &mut self,
+        zone: Zone,
+        index: usize,
+        edition: Option<Edition>,
+    ) -> Result<(), String> {
+        Self::check_index(self.cards_mut(zone).len(), index, "card")?;
+        self.checkpoint();
+        let card = &mut self.cards_mut(zone)[index];
+        *card = Card::new(card.rank, card.suit, card.enhancement, edition);
+        Ok(())
+    }
+
+    pub fn add_joker(&mut self, joker: JokerCard) {
+        self.checkpoint();
+        self.round.jokers.push(joker);
+    }
+
+    pub fn remove_joker(&mut self, index: usize) -> Result<JokerCard, String> {
+        Self::check_index(self.round.jokers.len(), index, "joker")?;
+        self.checkpoint();
+        Ok(self.round.jokers.remove(index))
+    }
+
+    /// Takes the joker at `from` out of the lineup and reinserts it at `to`.
+    pub fn move_joker(&mut self, from: usize, to: usize) -> Result<(), String> {
+        let len = self.round.jokers.len();
+        Self::check_index(len, from, "joker")?;
+        Self::check_index(len, to, "joker")?;
+        self.checkpoint();
+        let joker = self.round.jokers.remove(from);
+        self.round.jokers.insert(to, joker);
+        Ok(())
+    }
+
+    pub fn set_joker(&mut self, index: usize, joker: JokerCard) -> Result<(), String> {
+        Self::check_index(self.round.jokers.len(), index, "joker")?;
+        self.checkpoint();
+        self.round.jokers[index] = joker;
+        Ok(())
+    }
+
+    pub fn set_joker_edition(
+        &mut self,
+        index: usize,
+        edition: Option<Edition>,
+    ) -> Result<(), String> {
+        Self::check_index(self.round.jokers.len(), index, "joker")?;
+        self.checkpoint();
+        let joker = &mut self.round.jokers[index];
+        *joker = JokerCard::new(joker.joker, edition);
+        Ok(())
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(round) => {
+                self.round = round;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The full breakdown, or `None` while no cards are played (the engine
+    /// cannot classify an empty hand). A round loaded from a file may play
+    /// more than `MAX_PLAYED` cards, which the game does not allow, so that
+    /// is an error; so is any panic from the engine.
+    pub fn evaluate(&self) -> Result<Option<Sorce>, String> {
+        let played = self.round.cards_played.len();
+        if played == 0 {
+            return Ok(None);
+        }
+        if played > MAX_PLAYED {
+            return Err(format!(
+                "{played} cards played, at most {MAX_PLAYED} may be"
+            ));
+        }
+        let round = clone_round(&self.round);
+        panic::catch_unwind(|| crate::evaluate(round))
+            .map(Some)
+            .map_err(|err| {
+                err.downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| err.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "the engine panicked".to_string())
+            })
+    }
+
+    pub fn score(&self) -> Result<Option<f64>, String> {
+        Ok(self
+            .evaluate()?
+            .map(|sorce| (sorce.total_chips * sorce.mult).floor()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ortalib::{Rank, Suit};
+
+    fn card(rank: Rank) -> Card {
+        Card::new(rank, Suit::Spades, None, None)
+    }
+
+    fn session(played: usize, held: usize) -> Session {
+        Session::new(Round {
+            cards_played: (0..played).map(|_| card(Rank::Ace)).collect(),
+            cards_held_in_hand: (0..held).map(|_| card(Rank::Two)).collect(),
+            jokers: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn an_empty_hand_has_no_score() {
+        assert_eq!(session(0, 3).score(), Ok(None));
+    }
+
+    #[test]
+    fn adding_cards_stops_at_the_played_limit() {
+        let mut session = session(4, 0);
+        let err = session
+            .add_cards(Zone::Played, &[card(Rank::King), card(Rank::Queen)])
+            .unwrap_err();
+        assert_eq!(err, "at most 5 cards may be played (4 already are)");
+        assert_eq!(session.round.cards_played.len(), 4);
+        session
+            .add_cards(Zone::Played, &[card(Rank::King)])
+            .unwrap();
+        session.add_cards(Zone::Held, &[card(Rank::Queen)]).unwrap();
+    }
+
+    #[test]
+    fn moving_a_card_stops_at_the_played_limit() {
+        let mut session = session(5, 1);
+        assert!(session.move_card(Zone::Held, 0).is_err());
+        session.move_card(Zone::Played, 0).unwrap();
+        session.move_card(Zone::Held, 0).unwrap();
+        assert_eq!(session.round.cards_played.len(), 5);
+    }
+
+    #[test]
+    fn a_file_over_the_played_limit_is_an_error() {
+        assert_eq!(
+            session(6, 0).score(),
+            Err("6 cards played, at most 5 may be".to_string())
+        );
+        assert_eq!(session(2, 0).score(), Ok(Some(64.0)));
+    }
+}
@@ -0,0 +1,275 @@
+use super::catalog::{self, Rarity};
+use super::rng::SeededRng;
+use enum_iterator::all;
+use ortalib::{Edition, Joker, JokerCard};
+
+/// Jokers a player can own at once.
+pub const JOKER_SLOTS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShopConfig {
+    /// Jokers on offer after each reroll.
+    pub slots: usize,
+    /// Cost of the first reroll in a shop; each reroll adds $1.
+    pub reroll_base: i64,
+    /// Relative chance of each rarity per slot.
+    pub common_weight: f64,
+    pub uncommon_weight: f64,
+    pub rare_weight: f64,
+}
+
+impl Default for ShopConfig {
+    fn default() -> Self {
+        Self {
+            slots: 2,
+            reroll_base: 5,
+            common_weight: 70.0,
+            uncommon_weight: 25.0,
+            rare_weight: 5.0,
+        }
+    }
+}
+
+/// Chance of a shop joker having each edition, rolled in this order.
+const EDITION_ODDS: [(Edition, f64); 3] = [
+    (Edition::Polychrome, 0.003),
+    (Edition::Holographic, 0.014),
+    (Edition::Foil, 0.02),
+];
+
+/// What an edition adds to a joker's price.
+pub fn edition_cost(edition: Option<Edition>) -> i64 {
+    match edition {
+        None => 0,
+        Some(Edition::Foil) => 2,
+        Some(Edition::Holographic) => 3,
+        Some(Edition::Polychrome) => 5,
+    }
+}
+
+pub fn cost(card: &JokerCard) -> i64 {
+    i64::from(catalog::info(card.joker).cost) + edition_cost(card.edition)
+}
+
+/// Half the cost, rounded down, but never less than $1.
+pub fn sell_value(card: &JokerCard) -> i64 {
+    (cost(card) / 2).max(1)
+}
+
+/// Sells the joker at `index` for its sell value.
+pub fn sell(
+    jokers: &mut Vec<JokerCard>,
+    index: usize,
+    money: &mut i64,
+) -> Result<JokerCard, String> {
+    if index >= jokers.len() {
+        return Err(format!("there is no joker #{}", index + 1));
+    }
+    let card = jokers.remove(index);
+    *money += sell_value(&card);
+    Ok(card)
+}
+
+/// The jokers on sale between two blinds.
+#[derive(Debug, Clone)]
+pub struct Shop {
+    pub config: ShopConfig,
+    /// Unsold jokers; bought ones leave the list.
+    offers: Vec<JokerCard>,
+    rerolls: usize,
+    rng: SeededRng,
+}
+
+impl Shop {
+    /// Opens a shop, avoiding jokers the player already owns.
+    pub fn new(config: ShopConfig, owned: &[JokerCard], rng: SeededRng) -> Self {
+        let mut shop = Self {
+            config,
+            offers: Vec::new(),
+            rerolls: 0,
+            rng,
+        };
+        shop.restock(owned);
+        shop
+    }
+
+    pub fn offers(&self) -> &[JokerCard] {
+        &self.offers
+    }
+
+    pub fn reroll_cost(&self) -> i64 {
+        self.config.reroll_base + self.rerolls as i64
+    }
+
+    fn roll_rarity(&mut self) -> Rarity {
+        let c = &self.config;
+        let roll = self.rng.next_f64() * (c.common_weight + c.uncommon_weight + c.rare_weight);
+        if roll < c.common_weight {
+            Rarity::Common
+        } else if roll < c.common_weight + c.uncommon_weight {
+            Rarity::Uncommon
+        } else {
+            Rarity::Rare
+        }
+    }
+
+    fn roll_edition(&mut self) -> Option<Edition> {
+        let mut roll = self.rng.next_f64();
+        for (edition, odds) in EDITION_ODDS {
+            if roll < odds {
+                return Some(edition);
+            }
+            roll -= odds;
+        }
+        None
+    }
+
+    /// Fills every slot with a joker of a rolled rarity that is neither
+    /// owned nor already on offer. Jokers the scorer does not implement
+    /// are never stocked, since they would do nothing once bought. The
+    /// plain Joker fills in when a rarity has run out, as in the game.
+    fn restock(&mut self, owned: &[JokerCard]) {
+        self.offers.clear();
+        for _ in 0..self.config.slots {
+            let rarity = self.roll_rarity();
+            let taken: Vec<Joker> = owned
+                .iter()
+                .chain(&self.offers)
+                .map(|card| card.joker)
+                .collect();
+            let pool: Vec<Joker> = all::<Joker>()
+                .filter(|&joker| catalog::info(joker).rarity == rarity)
+                .filter(|&joker| catalog::is_implemented(joker))
+                .filter(|joker| !taken.contains(joker))
+                .collect();
+            let joker = if pool.is_empty() {
+                Joker::Joker
+            } else {
+                self.rng.choose(&pool)
+            };
+            let edition = self.roll_edition();
+            self.offers.push(JokerCard::new(joker, edition));
+        }
+    }
+
+    /// Pays for a new set of offers.
+    pub fn reroll(&mut self, owned: &[JokerCard], money: &mut i64) -> Result<i64, String> {
+        let price = self.reroll_cost();
+        if *money < price {
+            return Err(format!("a reroll costs ${price}, you have ${money}"));
+        }
+        *money -= price;
+        self.rerolls += 1;
+        self.restock(owned);
+        Ok(price)
+    }
+
+    /// Buys the offer at `index` into `jokers`, ready to score with.
+    pub fn buy(
+        &mut self,
+        index: usize,
+        jokers: &mut Vec<JokerCard>,
+        max_jokers: usize,
+        money: &mut i64,
+    ) -> Result<JokerCard, String> {
+        let Some(card) = self.offers.get(index).copied() else {
+            return Err(format!("there is no offer #{}", index + 1));
+        };
+        let price = cost(&card);
+        if *money < price {
+            return Err(format!("{card} costs ${price}, you have ${money}"));
+        }
+        if jokers.len() >= max_jokers {
+            return Err(format!("all {max_jokers} joker slots are full"));
+        }
+        *money -= price;
+        self.offers.remove(index);
+        jokers.push(card);
+        Ok(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(joker: Joker, edition: Option<Edition>) -> JokerCard {
+        JokerCard::new(joker, edition)
+    }
+
+    #[test]
+    fn editions_add_to_the_price() {
+        assert_eq!(cost(&card(Joker::Joker, None)), 2);
+        assert_eq!(cost(&card(Joker::Baron, Some(Edition::Foil))), 10);
+        assert_eq!(cost(&card(Joker::Baron, Some(Edition::Holographic))), 11);
+        assert_eq!(cost(&card(Joker::Baron, Some(Edition::Polychrome))), 13);
+    }
+
+    #[test]
+    fn sell_value_is_half_rounded_down_but_at_least_one() {
+        assert_eq!(sell_value(&card(Joker::Joker, None)), 1);
+        assert_eq!(sell_value(&card(Joker::JollyJoker, None)), 1);
+        assert_eq!(sell_value(&card(Joker::Baron, None)), 4);
+        assert_eq!(
+            sell_value(&card(Joker::Baron, Some(Edition::Polychrome))),
+            6
+        );
+    }
+
+    #[test]
+    fn selling_pays_out_and_frees_the_slot() {
+        let mut jokers = vec![card(Joker::Joker, None), card(Joker::Baron, None)];
+        let mut money = 0;
+        let sold = sell(&mut jokers, 1, &mut money).unwrap();
+        assert_eq!(sold.joker, Joker::Baron);
+        assert_eq!(money, 4);
+        assert_eq!(jokers.len(), 1);
+        assert!(sell(&mut jokers, 1, &mut money).is_err());
+    }
+
+    #[test]
+    fn only_implemented_unowned_jokers_are_stocked() {
+        let owned = [card(Joker::Joker, None), card(Joker::Baron, None)];
+        let config = ShopConfig {
+            slots: 4,
+            ..ShopConfig::default()
+        };
+        for seed in 0..200 {
+            let shop = Shop::new(config, &owned, SeededRng::new(seed));
+            let offered: Vec<Joker> = shop.offers().iter().map(|card| card.joker).collect();
+            assert_eq!(offered.len(), 4);
+            for (i, joker) in offered.iter().enumerate() {
+                assert!(catalog::is_implemented(*joker), "{joker} offered");
+                assert!(*joker == Joker::Joker || !offered[..i].contains(joker));
+                assert!(*joker == Joker::Joker || !owned.iter().any(|o| o.joker == *joker));
+            }
+        }
+    }
+
+    #[test]
+    fn rerolls_get_dearer_and_need_the_money() {
+        let mut shop = Shop::new(ShopConfig::default(), &[], SeededRng::new(3));
+        let mut money = 11;
+        assert_eq!(shop.reroll(&[], &mut money), Ok(5));
+        assert_eq!(shop.reroll_cost(), 6);
+        assert_eq!(shop.reroll(&[], &mut money), Ok(6));
+        assert_eq!(money, 0);
+        assert!(shop.reroll(&[], &mut money).is_err());
+        assert_eq!(shop.reroll_cost(), 7);
+    }
+
+    #[test]
+    fn buying_checks_money_and_slots() {
+        let mut shop = Shop::new(ShopConfig::default(), &[], SeededRng::new(5));
+        let offer = shop.offers()[0];
+        let mut jokers = Vec::new();
+        let mut money = cost(&offer) - 1;
+        assert!(shop.buy(0, &mut jokers, JOKER_SLOTS, &mut money).is_err());
+        money += 1;
+        assert!(shop.buy(0, &mut jokers, 0, &mut money).is_err());
+        assert_eq!(shop.buy(0, &mut jokers, JOKER_SLOTS, &mut money), Ok(offer));
+        assert_eq!(money, 0);
+        assert_eq!(jokers, vec![offer]);
+        assert_eq!(shop.offers().len(), 1);
+    }
+}
@@ -0,0 +1,214 @@
+use ortalib::{Card, JokerCard, Round};
+
+/// One removable piece of a round. Dropping a card also drops its
+/// enhancement and edition; dropping only the modifier keeps the bare card.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Part {
+    Played(usize),
+    PlayedEnhancement(usize),
+    PlayedEdition(usize),
+    Held(usize),
+    HeldEnhancement(usize),
+    HeldEdition(usize),
+    Joker(usize),
+    JokerEdition(usize),
+}
+
+fn parts_of(round: &Round) -> Vec<Part> {
+    let mut parts = Vec::new();
+    for (i, card) in round.cards_played.iter().enumerate() {
+        parts.push(Part::Played(i));
+        if card.enhancement.is_some() {
+            parts.push(Part::PlayedEnhancement(i));
+        }
+        if card.edition.is_some() {
+            parts.push(Part::PlayedEdition(i));
+        }
+    }
+    for (i, card) in round.cards_held_in_hand.iter().enumerate() {
+        parts.push(Part::Held(i));
+        if card.enhancement.is_some() {
+            parts.push(Part::HeldEnhancement(i));
+        }
+        if card.edition.is_some() {
+            parts.push(Part::HeldEdition(i));
+        }
+    }
+    for (i, joker) in round.jokers.iter().enumerate() {
+        parts.push(Part::Joker(i));
+        if joker.edition.is_some() {
+            parts.push(Part::JokerEdition(i));
+        }
+    }
+    parts
+}
+
+fn keep_cards(
+    cards: &[Card],
+    kept: &[Part],
+    card: fn(usize) -> Part,
+    enhancement: fn(usize) -> Part,
+    edition: fn(usize) -> Part,
+) -> Vec<Card> {
+    cards
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| kept.contains(&card(*i)))
+        .map(|(i, c)| {
+            Card::new(
+                c.rank,
+                c.suit,
+                c.enhancement.filter(|_| kept.contains(&enhancement(i))),
+                c.edition.filter(|_| kept.contains(&edition(i))),
+            )
+        })
+        .collect()
+}
+
+fn build(round: &Round, kept: &[Part]) -> Round {
+    Round {
+        cards_played: keep_cards(
+            &round.cards_played,
+            kept,
+            Part::Played,
+            Part::PlayedEnhancement,
+            Part::PlayedEdition,
+        ),
+        cards_held_in_hand: keep_cards(
+            &round.cards_held_in_hand,
+            kept,
+            Part::Held,
+            Part::HeldEnhancement,
+            Part::HeldEdition,
+        ),
+        jokers: round
+            .jokers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| kept.contains(&Part::Joker(*i)))
+            .map(|(i, j)| {
+                let edition = j.edition.filter(|_| kept.contains(&Part::JokerEdition(i)));
+                JokerCard::new(j.joker, edition)
+            })
+            .collect(),
+    }
+}
+
+/// Delta debugging (Zeller's `ddmin`) over the cards, jokers, enhancements
+/// and editions of `round`. Returns a 1-minimal round for which
+/// `still_fails` holds: removing any single remaining piece makes it pass.
+/// Candidates without a played card are never tried.
+pub fn shrink(round: &Round, still_fails: impl Fn(&Round) -> bool) -> Round {
+    let test = |kept: &[Part]| {
+        let candidate = build(round, kept);
+        !candidate.cards_played.is_empty() && still_fails(&candidate)
+    };
+
+    let mut kept = parts_of(round);
+    let mut granularity = 2;
+    while kept.len() >= 2 {
+        let chunk_len = kept.len().div_ceil(granularity);
+        let chunks: Vec<Vec<Part>> = kept.chunks(chunk_len).map(|c| c.to_vec()).collect();
+
+        let subset = chunks.iter().find(|chunk| test(chunk)).cloned();
+        let reduced = subset.or_else(|| {
+            chunks
+                .iter()
+                .map(|chunk| {
+                    kept.iter()
+                        .filter(|p| !chunk.contains(p))
+                        .copied()
+                        .collect::<Vec<_>>()
+                })
+                .find(|complement| test(complement))
+        });
+
+        match reduced {
+            Some(smaller) => {
+                kept = smaller;
+                granularity = (granularity - 1).max(2);
+            }
+            None if granularity >= kept.len() => break,
+            None => granularity = (granularity * 2).min(kept.len()),
+        }
+    }
+    build(round, &kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ortalib::{Edition, Enhancement, Joker, Rank, Suit};
+
+    fn round() -> Round {
+        Round {
+            cards_played: vec![
+                Card::new(Rank::Ace, Suit::Spades, Some(Enhancement::Bonus), None),
+                Card::new(Rank::King, Suit::Hearts, None, Some(Edition::Foil)),
+                Card::new(Rank::Two, Suit::Clubs, None, None),
+            ],
+            cards_held_in_hand: vec![
+                Card::new(Rank::Queen, Suit::Diamonds, Some(Enhancement::Steel), None),
+                Card::new(Rank::Three, Suit::Spades, None, Some(Edition::Holographic)),
+            ],
+            jokers: vec![
+                JokerCard::new(Joker::Joker, Some(Edition::Polychrome)),
+                JokerCard::new(Joker::Baron, None),
+            ],
+        }
+    }
+
+    #[test]
+    fn shrinks_to_the_failing_piece_and_one_played_card() {
+        let minimal = shrink(&round(), |r| {
+            r.jokers.iter().any(|j| j.joker == Joker::Baron)
+        });
+        assert_eq!(minimal.cards_played.len(), 1);
+        assert!(minimal.cards_held_in_hand.is_empty());
+        assert_eq!(minimal.jokers.len(), 1);
+        assert_eq!(minimal.jokers[0].joker, Joker::Baron);
+    }
+
+    #[test]
+    fn drops_a_modifier_but_keeps_its_card() {
+        let minimal = shrink(&round(), |r| {
+            r.cards_held_in_hand.iter().any(|c| c.rank == Rank::Three)
+        });
+        assert_eq!(minimal.cards_held_in_hand.len(), 1);
+        assert_eq!(minimal.cards_held_in_hand[0].rank, Rank::Three);
+        assert_eq!(minimal.cards_held_in_hand[0].edition, None);
+        assert!(minimal.jokers.is_empty());
+    }
+
+    #[test]
+    fn keeps_every_piece_the_failure_needs() {
+        let minimal = shrink(&round(), |r| {
+            r.cards_played
+                .iter()
+                .any(|c| c.enhancement == Some(Enhancement::Bonus))
+                && r.jokers
+                    .iter()
+                    .any(|j| j.edition == Some(Edition::Polychrome))
+        });
+        assert_eq!(minimal.cards_played.len(), 1);
+        assert_eq!(minimal.cards_played[0].rank, Rank::Ace);
+        assert_eq!(
+            minimal.cards_played[0].enhancement,
+            Some(Enhancement::Bonus)
+        );
+        assert_eq!(minimal.jokers.len(), 1);
+        assert_eq!(minimal.jokers[0].edition, Some(Edition::Polychrome));
+        assert!(minimal.cards_held_in_hand.is_empty());
+    }
+
+    #[test]
+    fn never_tries_a_round_without_played_cards() {
+        let minimal = shrink(&round(), |r| {
+            assert!(!r.cards_played.is_empty());
+            true
+        });
+        assert_eq!(minimal.cards_played.len(), 1);
+        assert!(minimal.cards_held_in_hand.is_empty());
+        assert!(minimal.jokers.is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+use super::round::clone_round;
+use ortalib::{Card, Round};
+
+/// Most cards a single hand may play.
+pub const MAX_PLAYED: usize = 5;
+
+/// Largest hand the exhaustive search accepts; 16 cards is under 7,000
+/// candidate plays.
+pub const MAX_HAND: usize = 16;
+
+/// The best way to split a hand into played and held cards.
+pub struct Solution {
+    pub round: Round,
+    pub score: f64,
+}
+
+/// Pools the played and held cards into one hand and scores every way of
+/// playing 1 to [`MAX_PLAYED`] of them, keeping the cards in their original
+/// order. Ties go to the play found first.
+pub fn solve(round: &Round) -> Result<Solution, String> {
+    let hand: Vec<Card> = round
+        .cards_played
+        .iter()
+        .chain(&round.cards_held_in_hand)
+        .copied()
+        .collect();
+    if hand.is_empty() {
+        return Err("the round has no cards to play".to_string());
+    }
+    if hand.len() > MAX_HAND {
+        return Err(format!(
+            "{} cards is too many to search, the limit is {MAX_HAND}",
+            hand.len()
+        ));
+    }
+
+    let mut best: Option<Solution> = None;
+    for mask in 1u32..1 << hand.len() {
+        if mask.count_ones() as usize > MAX_PLAYED {
+            continue;
+        }
+        let mut candidate = Round {
+            cards_played: Vec::new(),
+            cards_held_in_hand: Vec::new(),
+            jokers: round.jokers.clone(),
+        };
+        for (i, card) in hand.iter().enumerate() {
+            if mask & 1 << i != 0 {
+                candidate.cards_played.push(*card);
+            } else {
+                candidate.cards_held_in_hand.push(*card);
+            }
+        }
+        let (chips, mult) = crate::score(clone_round(&candidate));
+        let score = (chips * mult).floor();
+        if best.as_ref().is_none_or(|b| score > b.score) {
+            best = Some(Solution {
+                round: candidate,
+                score,
+            });
+        }
+    }
+    Ok(best.expect("a non-empty hand has at least one play"))
+}
@@ -1,235 +1,383 @@
 use super::jokers::HandJoker;
-use ortalib::{Chips, Edition, Enhancement, Joker, Mult, PokerHand, Rank, Suit};
+use super::rng::SeededRng;
+use ortalib::{Card, Chips, Edition, Enhancement, Joker, Mult, PokerHand, Rank, Suit};
 use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul};
+
+/// One change to the running chips / mult, in the order it was applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    AddChips(Chips),
+    AddMult(Mult),
+    TimesMult(Mult),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub source: String,
+    pub op: Op,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let op = match self.op {
+            Op::AddChips(chips) => format!("+{chips} Chips"),
+            Op::AddMult(mult) => format!("+{mult} Mult"),
+            Op::TimesMult(mult) => format!("x{mult} Mult"),
+        };
+        write!(f, "{op:<14} {}", self.source)
+    }
+}
+
+/// Running totals plus the trace of how they were reached.
+struct Tally {
+    total_chips: Chips,
+    mult: Mult,
+    steps: Vec<Step>,
+}
+
+impl Tally {
+    fn add_chips(&mut self, source: impl Display, chips: Chips) {
+        self.total_chips += chips;
+        self.push(source, Op::AddChips(chips));
+    }
+
+    fn add_mult(&mut self, source: impl Display, mult: Mult) {
+        self.mult += mult;
+        self.push(source, Op::AddMult(mult));
+    }
+
+    fn times_mult(&mut self, source: impl Display, mult: Mult) {
+        self.mult *= mult;
+        self.push(source, Op::TimesMult(mult));
+    }
+
+    fn push(&mut self, source: impl Display, op: Op) {
+        self.steps.push(Step {
+            source: source.to_string(),
+            op,
+        });
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Sorce {
+    pub hand: PokerHand,
     pub card_chips: Chips,
+    pub base_mult: Mult,
     pub total_chips: Chips,
     pub mult: Mult,
+    pub steps: Vec<Step>,
+    /// Scored cards that may be destroyed after scoring, with their chance.
+    pub at_risk: Vec<(Card, f64)>,
+    /// The cards from `at_risk` that the RNG actually destroyed; always
+    /// empty when scoring without an RNG.
+    pub destroyed: Vec<Card>,
 }
 
+/// A scored Glass card has a 1 in 4 chance to shatter once scoring is done.
+pub const GLASS_SHATTER_CHANCE: f64 = 0.25;
+
 impl Sorce {
     pub fn get_card(hand: HandJoker) -> Self {
-        let (card_chips, mut mult) = PokerHand::hand_value(&hand.hand);
-        let mut photograph_trigger = true;
-        let mut total_chips: f64 = 0.0;
-        total_chips += card_chips;
+        Self::get_card_seeded(hand, None)
+    }
 
-        for card in &hand.cards_impl {
+    /// Scores the hand, then rolls every chance-based effect on `rng`, so
+    /// the same seed always destroys the same cards.
+    pub fn get_card_seeded(hand: HandJoker, rng: Option<&mut SeededRng>) -> Self {
+        let (card_chips, base_mult) = PokerHand::hand_value(&hand.hand);
+        let first_face = hand.cards_impl.iter().position(|c| c.rank.is_face());
+        let mut tally = Tally {
+            total_chips: card_chips,
+            mult: base_mult,
+            steps: Vec::new(),
+        };
+
+        for (i, card) in hand.cards_impl.iter().enumerate() {
             let rank_value = card.rank.rank_value();
+            tally.add_chips(card, rank_value);
             if let Some(enhancement) = card.enhancement {
                 match enhancement {
-                    Enhancement::Bonus => {
-                        total_chips += rank_value + 30.0;
-                    }
-                    Enhancement::Mult => {
-                        mult += 4.0;
-                        total_chips += rank_value;
-                    }
-                    Enhancement::Glass => {
-                        mult *= 2.0;
-                        total_chips += rank_value;
-                    }
-                    _ => {
-                        total_chips += rank_value;
-                    }
+                    Enhancement::Bonus => tally.add_chips(card, 30.0),
+                    Enhancement::Mult => tally.add_mult(card, 4.0),
+                    Enhancement::Glass => tally.times_mult(card, 2.0),
+                    _ => {}
                 }
-            } else {
-                total_chips += rank_value;
             }
 
             if let Some(edit) = card.edition {
                 match edit {
-                    Edition::Foil => {
-                        total_chips += 50.0;
-                    }
-                    Edition::Holographic => {
-                        mult += 10.0;
-                    }
-                    Edition::Polychrome => mult *= 1.5,
+                    Edition::Foil => tally.add_chips(card, 50.0),
+                    Edition::Holographic => tally.add_mult(card, 10.0),
+                    Edition::Polychrome => tally.times_mult(card, 1.5),
                 }
             }
             for joker_card in &hand.work_joker_cards_in_hand {
+                let source = format!("{} ({card})", joker_card.joker);
                 match joker_card.joker {
-                    Joker::GreedyJoker => {
-                        if card.suit == Suit::Diamonds {
-                            mult += 3.0
-                        }
+                    Joker::GreedyJoker if Self::has_suit(card, Suit::Diamonds) => {
+                        tally.add_mult(source, 3.0)
                     }
-                    Joker::LustyJoker => {
-                        if card.suit == Suit::Hearts {
-                            mult += 3.0
-                        }
+                    Joker::LustyJoker if Self::has_suit(card, Suit::Hearts) => {
+                        tally.add_mult(source, 3.0)
                     }
-                    Joker::WrathfulJoker => {
-                        if card.suit == Suit::Spades {
-                            mult += 3.0
-                        }
+                    Joker::WrathfulJoker if Self::has_suit(card, Suit::Spades) => {
+                        tally.add_mult(source, 3.0)
                     }
-                    Joker::GluttonousJoker => {
-                        if card.suit == Suit::Clubs {
-                            mult += 3.0
-                        }
+                    Joker::GluttonousJoker if Self::has_suit(card, Suit::Clubs) => {
+                        tally.add_mult(source, 3.0)
                     }
-                    Joker::Fibonacci => {
+                    Joker::Fibonacci
                         if card.rank.rank_value() == 2.0
                             || card.rank.rank_value() == 3.0
                             || card.rank.rank_value() == 5.0
                             || card.rank.rank_value() == 8.0
-                            || card.rank.rank_value() == 11.0
-                        {
-                            mult += 8.0
-                        }
+                            || card.rank.rank_value() == 11.0 =>
+                    {
+                        tally.add_mult(source, 8.0)
                     }
-                    Joker::ScaryFace => {
+                    Joker::ScaryFace
                         if card.rank == Rank::Jack
                             || card.rank == Rank::Queen
-                            || card.rank == Rank::King
-                        {
-                            total_chips += 30.0
-                        }
-                    }
-                    Joker::EvenSteven => {
-                        if card.rank.rank_value() == 10.0
-                            || card.rank.rank_value() == 8.0
-                            || card.rank.rank_value() == 6.0
-                            || card.rank.rank_value() == 4.0
-                            || card.rank.rank_value() == 2.0
-                        {
-                            mult += 4.0
-                        }
+                            || card.rank == Rank::King =>
+                    {
+                        tally.add_chips(source, 30.0)
                     }
-                    Joker::OddTodd => {
-                        if card.rank.rank_value() == 11.0
-                            || card.rank.rank_value() == 9.0
-                            || card.rank.rank_value() == 7.0
-                            || card.rank.rank_value() == 5.0
-                            || card.rank.rank_value() == 3.0
-                        {
-                            mult += 4.0
-                        }
+                    // By rank, not chip value: face cards are worth 10 but
+                    // are not even.
+                    Joker::EvenSteven
+                        if matches!(
+                            card.rank,
+                            Rank::Ten | Rank::Eight | Rank::Six | Rank::Four | Rank::Two
+                        ) =>
+                    {
+                        tally.add_mult(source, 4.0)
                     }
-                    Joker::Photograph => {
-                        if (card.rank == Rank::Jack
-                            || card.rank == Rank::Queen
-                            || card.rank == Rank::King)
-                            && photograph_trigger
-                        {
-                            mult *= 2.0;
-                            photograph_trigger = false;
-                        }
+                    Joker::OddTodd
+                        if matches!(
+                            card.rank,
+                            Rank::Ace | Rank::Nine | Rank::Seven | Rank::Five | Rank::Three
+                        ) =>
+                    {
+                        tally.add_chips(source, 31.0)
                     }
-                    Joker::SmileyFace => {
+                    // Every Photograph fires on the same card.
+                    Joker::Photograph if first_face == Some(i) => tally.times_mult(source, 2.0),
+                    Joker::SmileyFace
                         if card.rank == Rank::Jack
                             || card.rank == Rank::Queen
-                            || card.rank == Rank::King
-                        {
-                            mult += 5.0
-                        }
+                            || card.rank == Rank::King =>
+                    {
+                        tally.add_mult(source, 5.0)
                     }
                     _ => {}
                 }
             }
         }
 
-        for hold_card in &hand.cards_hold_in_hand {
-            if let Some(enhancement) = hold_card.enhancement {
-                match enhancement {
-                    Enhancement::Steel => {
-                        mult *= 1.5;
+        // Held cards, left to right: Steel, then each on-held joker. Mime
+        // retriggers the whole card.
+        let mimes = hand
+            .work_joker_cards_in_hand
+            .iter()
+            .filter(|j| j.joker == Joker::Mime)
+            .count();
+        let lowest = Self::lowest_held(&hand.cards_hold_in_hand);
+        for (i, hold_card) in hand.cards_hold_in_hand.iter().enumerate() {
+            for retrigger in 0..=mimes {
+                let held = if retrigger == 0 {
+                    format!("{hold_card} held")
+                } else {
+                    format!("{hold_card} held, Mime retrigger")
+                };
+                if hold_card.enhancement == Some(Enhancement::Steel) {
+                    tally.times_mult(&held, 1.5);
+                }
+                for joker_card in &hand.work_joker_cards_in_hand {
+                    match joker_card.joker {
+                        Joker::Baron if hold_card.rank == Rank::King => {
+                            tally.times_mult(format!("Baron ({held})"), 1.5)
+                        }
+                        Joker::RaisedFist if lowest == Some(i) => tally.add_mult(
+                            format!("Raised Fist (lowest {held})"),
+                            2.0 * hold_card.rank.rank_value(),
+                        ),
+                        _ => {}
                     }
-                    _ => {}
                 }
             }
         }
-        for joker_card in &hand.work_joker_cards_in_hand {
-            match joker_card.joker {
-                Joker::Baron => {
-                    let counts = hand
-                        .cards_hold_in_hand
-                        .iter()
-                        .filter(|c| c.rank == Rank::King)
-                        .count() as f64;
-                    if counts != 0.0 {
-                        mult *= 1.5f64.powi(counts as i32);
-                    }
-                }
+        // Jokers, left to right: Foil and Holographic, the joker's own
+        // effect if it applies to this hand, then Polychrome.
+        for joker_card in &hand.joker_card {
+            match joker_card.edition {
+                Some(Edition::Foil) => tally.add_chips(joker_card, 50.0),
+                Some(Edition::Holographic) => tally.add_mult(joker_card, 10.0),
                 _ => {}
             }
-        }
-        for joker_card in &hand.work_joker_cards_in_hand {
-            match joker_card.joker {
-                Joker::Joker => {
-                    mult += 4.0;
-                }
-                Joker::JollyJoker => mult += 8.0,
-                Joker::ZanyJoker => mult += 12.0,
-                Joker::MadJoker => mult += 10.0,
-                Joker::CrazyJoker => mult += 12.0,
-                Joker::DrollJoker => mult += 10.0,
-                Joker::SlyJoker => total_chips += 50.0,
-                Joker::WilyJoker => total_chips += 100.0,
-                Joker::CleverJoker => total_chips += 80.0,
-                Joker::DeviousJoker => total_chips += 100.0,
-                Joker::CraftyJoker => total_chips += 80.0,
+            let joker = joker_card.joker;
+            let works = hand.work_joker_cards_in_hand.contains(joker_card);
+            match joker {
+                _ if !works => {}
+                Joker::Joker => tally.add_mult(joker, 4.0),
+                Joker::JollyJoker => tally.add_mult(joker, 8.0),
+                Joker::ZanyJoker => tally.add_mult(joker, 12.0),
+                Joker::MadJoker => tally.add_mult(joker, 10.0),
+                Joker::CrazyJoker => tally.add_mult(joker, 12.0),
+                Joker::DrollJoker => tally.add_mult(joker, 10.0),
+                Joker::SlyJoker => tally.add_chips(joker, 50.0),
+                Joker::WilyJoker => tally.add_chips(joker, 100.0),
+                Joker::CleverJoker => tally.add_chips(joker, 80.0),
+                Joker::DeviousJoker => tally.add_chips(joker, 100.0),
+                Joker::CraftyJoker => tally.add_chips(joker, 80.0),
                 Joker::AbstractJoker => {
-                    mult += 3.0 * (hand.total_joker_number as f64);
-                },
-                Joker::RaisedFist => {
-                    let len_card_hold_in_hand = hand.cards_hold_in_hand.len();
-                    let last_one = hand.cards_hold_in_hand[len_card_hold_in_hand - 1].rank;
-                    mult += 2.0 * last_one.rank_value()
+                    tally.add_mult(joker, 3.0 * (hand.total_joker_number as f64));
                 }
-                Joker::Blackboard => {
-                    if hand
-                        .cards_hold_in_hand
-                        .iter()
-                        .all(|c| c.suit == Suit::Clubs || c.suit == Suit::Spades)
-                    {
-                        mult *= 3.0
-                    }
+                Joker::Blackboard
+                    if hand.cards_hold_in_hand.iter().all(|c| {
+                        Self::has_suit(c, Suit::Clubs) || Self::has_suit(c, Suit::Spades)
+                    }) =>
+                {
+                    tally.times_mult(joker, 3.0)
                 }
-                Joker::FlowerPot => {
-                    if hand.cards_impl.len() >= 4 {
-                        let mut fixed_suits = HashSet::new();
-                        let mut flexible_count = 0;
-
-                        for card in &hand.cards_impl {
-                            if matches!(card.enhancement, Some(Enhancement::Wild)) {
-                                flexible_count += 1;
-                            } else {
-                                fixed_suits.insert(card.suit);
-                            }
-                        }
-                        let missing_suits = 4 - fixed_suits.len();
+                Joker::FlowerPot if hand.cards_impl.len() >= 4 => {
+                    let mut fixed_suits = HashSet::new();
+                    let mut flexible_count = 0;
 
-                        if missing_suits <= 0 || flexible_count >= missing_suits {
-                            mult *= 3.0
+                    for card in &hand.cards_impl {
+                        if matches!(card.enhancement, Some(Enhancement::Wild)) {
+                            flexible_count += 1;
+                        } else {
+                            fixed_suits.insert(card.suit);
                         }
                     }
-                }
-                _ => {}
-            }
-        }
+                    let missing_suits = 4 - fixed_suits.len();
 
-        for hold_joker_card in &hand.joker_card {
-            if let Some(editions) = hold_joker_card.edition {
-                match editions {
-                    Edition::Foil => {
-                        total_chips += 50.0;
-                    }
-                    Edition::Holographic => {
-                        mult += 10.0;
+                    if missing_suits == 0 || flexible_count >= missing_suits {
+                        tally.times_mult(joker, 3.0)
                     }
-                    Edition::Polychrome => mult *= 1.5,
                 }
+                _ => {}
+            }
+            if joker_card.edition == Some(Edition::Polychrome) {
+                tally.times_mult(joker_card, 1.5);
             }
         }
+
+        let at_risk: Vec<(Card, f64)> = hand
+            .cards_impl
+            .iter()
+            .filter(|c| c.enhancement == Some(Enhancement::Glass))
+            .map(|c| (*c, GLASS_SHATTER_CHANCE))
+            .collect();
+        let destroyed = match rng {
+            Some(rng) => at_risk
+                .iter()
+                .filter(|(_, chance)| rng.chance(*chance))
+                .map(|(card, _)| *card)
+                .collect(),
+            None => Vec::new(),
+        };
+
         Self {
+            hand: hand.hand,
             card_chips,
-            total_chips,
-            mult,
+            base_mult,
+            total_chips: tally.total_chips,
+            mult: tally.mult,
+            steps: tally.steps,
+            at_risk,
+            destroyed,
+        }
+    }
+
+    /// Wild cards count as every suit.
+    fn has_suit(card: &Card, suit: Suit) -> bool {
+        card.suit == suit || card.enhancement == Some(Enhancement::Wild)
+    }
+
+    /// Index of the lowest ranked held card; ties go to the rightmost one.
+    fn lowest_held(cards: &[Card]) -> Option<usize> {
+        let mut lowest: Option<usize> = None;
+        for (i, card) in cards.iter().enumerate() {
+            if lowest.is_none_or(|l| card.rank <= cards[l].rank) {
+                lowest = Some(i);
+            }
+        }
+        lowest
+    }
+
+    /// Re-runs the recorded steps in another number type, e.g. one that
+    /// cannot overflow.
+    pub fn replay<T>(&self) -> (T, T)
+    where
+        T: From<f64> + Add<Output = T> + Mul<Output = T>,
+    {
+        let mut chips = T::from(self.card_chips);
+        let mut mult = T::from(self.base_mult);
+        for step in &self.steps {
+            match step.op {
+                Op::AddChips(x) => chips = chips + T::from(x),
+                Op::AddMult(x) => mult = mult + T::from(x),
+                Op::TimesMult(x) => mult = mult * T::from(x),
+            }
         }
+        (chips, mult)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ortalib::Round;
+
+    /// Floored score of a round given as YAML flow lists.
+    fn score(played: &str, held: &str, jokers: &str) -> f64 {
+        let yaml =
+            format!("cards_played: [{played}]\ncards_held_in_hand: [{held}]\njokers: [{jokers}]");
+        let round: Round = serde_yaml::from_str(&yaml).unwrap();
+        let (chips, mult) = crate::score(round);
+        (chips * mult).floor()
+    }
+
+    #[test]
+    fn odd_todd_gives_chips_for_odd_ranks() {
+        // 5 + 9 + 31 Chips x 1 Mult; the Ace counts as odd too.
+        assert_eq!(score("9♠", "", "Odd Todd"), 45.0);
+        assert_eq!(score("A♠", "", "Odd Todd"), 47.0);
+        assert_eq!(score("8♠", "", "Odd Todd"), 13.0);
+    }
+
+    #[test]
+    fn even_steven_goes_by_rank_not_chips() {
+        // A King is worth 10 chips but is not an even rank.
+        assert_eq!(score("K♠", "", "Even Steven"), 15.0);
+        assert_eq!(score("10♠", "", "Even Steven"), 75.0);
+    }
+
+    #[test]
+    fn wild_cards_count_as_every_suit() {
+        // 16 Chips x (1 + 3) Mult from the Wild Ace of Spades.
+        assert_eq!(score("A♠ Wild", "", "Greedy Joker"), 64.0);
+        assert_eq!(score("A♠", "Q♥ Wild", "Blackboard"), 48.0);
+        assert_eq!(score("A♠", "Q♥", "Blackboard"), 16.0);
+    }
+
+    #[test]
+    fn every_photograph_doubles_the_first_face_card() {
+        // Pair of Kings: 30 Chips x 2 Mult, doubled once per Photograph on
+        // the first King only.
+        assert_eq!(score("K♠, K♥", "", "Photograph"), 120.0);
+        assert_eq!(score("K♠, K♥", "", "Photograph, Photograph"), 240.0);
+    }
+
+    #[test]
+    fn joker_editions_apply_at_their_own_slot() {
+        // (1 + 4) x 1.5 + 4 = 11.5 Mult; applying the Polychrome after
+        // every joker would give (1 + 4 + 4) x 1.5 = 13.5.
+        assert_eq!(score("A♠", "", "Joker Polychrome, Joker"), 184.0);
+        // Holographic's +10 Mult lands before Blackboard's x3.
+        assert_eq!(score("A♠", "", "Joker Holographic, Blackboard"), 720.0);
     }
 }
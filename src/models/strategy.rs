@@ -0,0 +1,143 @@
+use super::blind::MAX_SELECTED;
+use super::catalog;
+use super::consumables::Tarot;
+use super::rng::SeededRng;
+use super::run::{BlindAction, BlindView, CONSUMABLE_SLOTS, Offer, ShopAction, ShopView, Strategy};
+use super::solver;
+use ortalib::{Card, Round};
+
+/// The hand positions of the best play `solver` finds, and its score.
+fn best_play(view: &BlindView) -> (Vec<usize>, f64) {
+    let round = Round {
+        cards_played: view.hand.to_vec(),
+        cards_held_in_hand: Vec::new(),
+        jokers: view.jokers.to_vec(),
+    };
+    match solver::solve(&round) {
+        Ok(solution) => {
+            let indices = solution
+                .round
+                .cards_played
+                .iter()
+                .filter_map(|card| view.hand.iter().position(|c| c == card))
+                .collect();
+            (indices, solution.score)
+        }
+        Err(_) => ((0..view.hand.len().min(MAX_SELECTED)).collect(), 0.0),
+    }
+}
+
+/// Tarots whose enhancement always helps a scoring card.
+const SCORING_TAROTS: [Tarot; 3] = [Tarot::TheEmpress, Tarot::TheHierophant, Tarot::Justice];
+
+/// Plays the best hand `solver` finds, discarding the rest when that hand
+/// would not keep pace with the target. Buys the dearest jokers it can
+/// afford, then planets, then tarots that enhance scoring cards.
+#[derive(Debug, Clone, Default)]
+pub struct Greedy;
+
+impl Strategy for Greedy {
+    fn name(&self) -> &str {
+        "greedy"
+    }
+
+    fn blind_action(&mut self, view: &BlindView) -> BlindAction {
+        let (play, score) = best_play(view);
+        if let Some(slot) = view.tarots.iter().position(|t| SCORING_TAROTS.contains(t)) {
+            let (_, max) = view.tarots[slot].targets();
+            return BlindAction::UseTarot(slot, play.iter().copied().take(max).collect());
+        }
+
+        let needed = view.target - view.score;
+        let on_pace = score * view.hands_left as f64 >= needed;
+        let mut rest: Vec<usize> = (0..view.hand.len()).filter(|i| !play.contains(i)).collect();
+        if on_pace || view.discards_left == 0 || rest.is_empty() {
+            return BlindAction::Play(play);
+        }
+        rest.sort_by_key(|&i| view.hand[i].rank);
+        rest.truncate(MAX_SELECTED);
+        BlindAction::Discard(rest)
+    }
+
+    fn shop_action(&mut self, view: &ShopView) -> ShopAction {
+        let affordable = |i: &usize| view.offers[*i].cost() <= view.money;
+        let indices = || (0..view.offers.len()).filter(affordable);
+
+        let joker = indices()
+            .filter(|_| view.jokers.len() < view.joker_slots)
+            .filter(|&i| match view.offers[i] {
+                Offer::Joker(card) => catalog::is_implemented(card.joker),
+                _ => false,
+            })
+            .max_by_key(|&i| view.offers[i].cost());
+        let planet = indices().find(|&i| matches!(view.offers[i], Offer::Planet(_)));
+        let tarot = indices()
+            .filter(|_| view.tarots.len() < CONSUMABLE_SLOTS)
+            .find(|&i| matches!(view.offers[i], Offer::Tarot(t) if SCORING_TAROTS.contains(&t)));
+        joker
+            .or(planet)
+            .or(tarot)
+            .map_or(ShopAction::Leave, ShopAction::Buy)
+    }
+}
+
+/// Picks uniformly among allowed actions; a baseline for other strategies.
+#[derive(Debug, Clone)]
+pub struct Random {
+    rng: SeededRng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    /// `count` distinct positions in `hand`, in random order.
+    fn pick(&mut self, hand: &[Card], count: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..hand.len()).collect();
+        self.rng.shuffle(&mut indices);
+        indices.truncate(count);
+        indices
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn blind_action(&mut self, view: &BlindView) -> BlindAction {
+        if let Some(&tarot) = view.tarots.first() {
+            let (min, max) = tarot.targets();
+            if view.hand.len() >= min {
+                let count = self.rng.range(min, max.min(view.hand.len()));
+                return BlindAction::UseTarot(0, self.pick(view.hand, count));
+            }
+        }
+        let count = self.rng.range(1, view.hand.len().min(MAX_SELECTED));
+        let cards = self.pick(view.hand, count);
+        if view.discards_left > 0 && self.rng.chance(0.5) {
+            BlindAction::Discard(cards)
+        } else {
+            BlindAction::Play(cards)
+        }
+    }
+
+    fn shop_action(&mut self, view: &ShopView) -> ShopAction {
+        let buyable: Vec<usize> = (0..view.offers.len())
+            .filter(|&i| view.offers[i].cost() <= view.money)
+            .filter(|&i| match view.offers[i] {
+                Offer::Joker(_) => view.jokers.len() < view.joker_slots,
+                Offer::Tarot(_) => view.tarots.len() < CONSUMABLE_SLOTS,
+                Offer::Planet(_) => true,
+            })
+            .collect();
+        if buyable.is_empty() || self.rng.chance(0.5) {
+            ShopAction::Leave
+        } else {
+            ShopAction::Buy(self.rng.choose(&buyable))
+        }
+    }
+}
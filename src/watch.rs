@@ -0,0 +1,137 @@
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+/// How long to keep collecting events after the first one, so an editor's
+/// write-rename-chmod burst rescores a file once.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// A path given on the command line, resolved so event paths can be matched
+/// against it.
+enum Target {
+    File(PathBuf),
+    Dir(PathBuf),
+}
+
+impl Target {
+    fn matches(&self, path: &Path) -> bool {
+        match self {
+            Target::File(file) => path == file,
+            Target::Dir(dir) => path.starts_with(dir) && is_round_file(path),
+        }
+    }
+}
+
+fn is_round_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yml" | "yaml")
+    )
+}
+
+fn resolve(path: &Path) -> Result<Target, Box<dyn Error>> {
+    let path = fs::canonicalize(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    if path.is_dir() {
+        Ok(Target::Dir(path))
+    } else {
+        Ok(Target::File(path))
+    }
+}
+
+/// Every round file under a directory, recursively.
+fn round_files(dir: &Path, files: &mut BTreeSet<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            round_files(&path, files);
+        } else if is_round_file(&path) {
+            files.insert(path);
+        }
+    }
+}
+
+/// Scores each file, printing the change from its last good score. Parse
+/// errors and engine panics are reported and the previous score is kept.
+fn rescore(
+    files: &BTreeSet<PathBuf>,
+    last: &mut HashMap<PathBuf, f64>,
+    score: &impl Fn(&Path) -> Result<f64, Box<dyn Error>>,
+) {
+    for file in files {
+        println!("== {} ==", file.display());
+        match panic::catch_unwind(AssertUnwindSafe(|| score(file))) {
+            Ok(Ok(total)) => {
+                if let Some(before) = last.insert(file.clone(), total) {
+                    println!("change: {:+} (was {before})", total - before);
+                }
+            }
+            Ok(Err(err)) => eprintln!("error: {err}"),
+            Err(_) => eprintln!("error: the engine panicked on this round"),
+        }
+    }
+}
+
+/// Scores every round under `paths`, then rescores whichever of them change
+/// until interrupted.
+pub fn watch(
+    paths: &[PathBuf],
+    score: impl Fn(&Path) -> Result<f64, Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    let targets = paths
+        .iter()
+        .map(|p| resolve(p))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let mut initial = BTreeSet::new();
+    for target in &targets {
+        match target {
+            // Editors often save by renaming a new file over the old one,
+            // which would end a watch on the file itself.
+            Target::File(file) => {
+                let parent = file.parent().unwrap_or(Path::new("/"));
+                watcher.watch(parent, RecursiveMode::NonRecursive)?;
+                initial.insert(file.clone());
+            }
+            Target::Dir(dir) => {
+                watcher.watch(dir, RecursiveMode::Recursive)?;
+                round_files(dir, &mut initial);
+            }
+        }
+    }
+
+    let mut last = HashMap::new();
+    rescore(&initial, &mut last, &score);
+
+    while let Ok(first) = rx.recv() {
+        let mut changed = BTreeSet::new();
+        let mut pending = Some(first);
+        while let Some(result) = pending {
+            match result {
+                Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    changed.extend(
+                        event
+                            .paths
+                            .into_iter()
+                            .filter(|p| p.is_file() && targets.iter().any(|t| t.matches(p))),
+                    );
+                }
+                Ok(_) => {}
+                Err(err) => eprintln!("error: {err}"),
+            }
+            pending = rx.recv_timeout(DEBOUNCE).ok();
+        }
+        rescore(&changed, &mut last, &score);
+    }
+    Ok(())
+}